@@ -0,0 +1,98 @@
+use std::cmp::Reverse;
+
+use crate::models::{student_project::StudentProjectSubmission, config::AssignmentConfig, commit_info::Identity, authorship_report::{AuthorshipReport, IdentityShare}};
+
+
+// what is known about who should have made the commits of a submission
+struct Student {
+    // name Moodle puts before the first '_' of the submission folder
+    name: String,
+    emails: Vec<String>,
+    usernames: Vec<String>,
+}
+
+// commit_infos of jordan's submissions only hold the commits touching the
+// student's folder, classmates in the shared repo are not counted
+pub fn check_authorship(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    for submission in submissions.iter_mut() {
+        if !submission.cloned || submission.commit_infos.is_empty() {
+            continue;
+        }
+        let student = find_student(submission, config);
+        // without an email or username there is nothing to match the identities with
+        let is_known = !student.emails.is_empty() || !student.usernames.is_empty();
+
+        let mut report = AuthorshipReport::default();
+        for commit in submission.commit_infos.iter() {
+            identity_share(&mut report.identities, &commit.author).authored += 1;
+            identity_share(&mut report.identities, &commit.committer).committed += 1;
+            for co_author in commit.co_authors.iter() {
+                identity_share(&mut report.identities, co_author).co_authored += 1;
+            }
+            if is_known && !matches_student(&commit.author, &student) {
+                report.foreign_commits.push(commit.hash.clone());
+            }
+        }
+
+        let total = submission.commit_infos.len() as f64;
+        for share in report.identities.iter_mut() {
+            share.share = share.authored as f64 / total * 100.0;
+            share.matches_student = if is_known {
+                Some(matches_student(&share.identity, &student))
+            } else {
+                None
+            };
+        }
+        report.identities.sort_by_key(|s| Reverse(s.authored));
+        submission.authorship = Some(report);
+    }
+}
+
+fn find_student(submission: &StudentProjectSubmission, config: &AssignmentConfig) -> Student {
+    let folder = submission.student_folder.rsplit('/').next().unwrap_or_default();
+    let name = folder.split('_').next().unwrap_or_default().trim().to_lowercase();
+    let mut student = Student { name, emails: vec![], usernames: vec![] };
+
+    if let Some(entry) = config.roster.iter().find(|e| e.name.to_lowercase() == student.name) {
+        student.emails.extend(entry.email.iter().map(|e| e.to_lowercase()));
+        student.usernames.extend(entry.username.iter().map(|u| u.to_lowercase()));
+    }
+    // https://github.com/<owner>/<repo>.git
+    if let Some(owner) = submission.git_repo.as_ref().and_then(|r| r.split('/').nth(3)) {
+        student.usernames.push(owner.to_lowercase());
+    }
+    student
+}
+
+fn matches_student(identity: &Identity, student: &Student) -> bool {
+    let email = identity.email.to_lowercase();
+    let name = identity.name.to_lowercase();
+    // GitHub's private addresses look like 12345+username@users.noreply.github.com
+    let user = email.split('@').next().unwrap_or_default();
+    let user = user.rsplit('+').next().unwrap_or_default();
+
+    student.emails.contains(&email)
+        || student.usernames.iter().any(|u| u == user || *u == name)
+        || name == student.name
+}
+
+fn identity_share<'a>(identities: &'a mut Vec<IdentityShare>, identity: &Identity) -> &'a mut IdentityShare {
+    let position = identities
+        .iter()
+        .position(|s| s.identity.name == identity.name && s.identity.email.eq_ignore_ascii_case(&identity.email));
+    let index = match position {
+        Some(i) => i,
+        None => {
+            identities.push(IdentityShare {
+                identity: identity.clone(),
+                authored: 0,
+                committed: 0,
+                co_authored: 0,
+                share: 0.0,
+                matches_student: None,
+            });
+            identities.len() - 1
+        },
+    };
+    &mut identities[index]
+}
@@ -0,0 +1,220 @@
+use std::{fs, io::{Error, ErrorKind}};
+
+use crate::models::{student_project::StudentProjectSubmission, config::{AssignmentConfig, ResourceLimits, TaskConfig}, benchmark_result::BenchmarkResult};
+
+use super::{
+    validator::check_dir_exists,
+    config_handler::get_task_language,
+    git_compilation_handler::{build_task, get_submission_main_file, workspace_name},
+    os_helper::run_limited_command,
+    parser::escape,
+    stats::median,
+};
+
+// cpu seconds and peak resident set size in kB
+struct Measurement {
+    cpu_secs: f64,
+    peak_rss_kb: u64,
+}
+
+enum BenchmarkRun {
+    Measured(Measurement),
+    // benchmark input of the run
+    TimedOut(String),
+    // benchmark input and exit status, None if killed by a signal
+    Crashed(String, Option<i32>),
+}
+
+pub fn run_benchmarks(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[BENCHMARK] Error reading repos directory!");
+        std::process::exit(1);
+    }
+
+    // the reference is measured once per task
+    let reference_task1 = measure_reference("task1", &config.task1, config);
+    let reference_task2 = measure_reference("task2", &config.task2, config);
+
+    for submission in submissions.iter_mut() {
+        if !submission.cloned {
+            continue;
+        }
+
+        let tasks = [
+            (submission.has_task1.clone(), &config.task1, &reference_task1),
+            (submission.has_task2.clone(), &config.task2, &reference_task2),
+        ];
+        for (task, task_config, reference) in tasks.iter() {
+            let (task, reference) = match (task, reference) {
+                (Some(task), Some(reference)) => (task, reference),
+                _ => continue,
+            };
+            let task_main_file = match get_submission_main_file(submission, task) {
+                Some(main) => main,
+                None => continue,
+            };
+            let language = get_task_language(config, submission, task);
+
+            // optimised like the reference, the other stages build without flags
+            let run_command = match build_task(
+                language.as_ref(),
+                &submission.student_folder,
+                task,
+                &task_main_file,
+                "-O2",
+                "benchmark",
+                &language.limits(&config.compile_limits)
+            ) {
+                Ok(c) => c,
+                Err(e) => {
+                    println!("[BENCHMARK] Error building task ({}) of ({}): {:#?}", task, submission.student_folder, e);
+                    if e.kind() == ErrorKind::TimedOut {
+                        submission.timeouts.push(format!("benchmark build {}", task));
+                    }
+                    continue;
+                },
+            };
+
+            let time_file = format!(
+                "./rp_workspace/run/{}.time",
+                workspace_name(&submission.student_folder, task, "benchmark")
+            );
+            let measurement = match measure_inputs(&run_command, task_config, &time_file, &language.limits(&config.run_limits)) {
+                Ok(BenchmarkRun::Measured(m)) => m,
+                Ok(BenchmarkRun::TimedOut(input)) => {
+                    println!("[BENCHMARK] Task ({}) of ({}) timed out on ({})", task, submission.student_folder, input);
+                    submission.timeouts.push(format!("benchmark {}", task));
+                    continue;
+                },
+                Ok(BenchmarkRun::Crashed(input, status)) => {
+                    let failure = match status {
+                        Some(code) => format!("exit status {} on {}", code, input),
+                        None => format!("killed by a signal on {}", input),
+                    };
+                    println!("[BENCHMARK] Task ({}) of ({}) failed: {}", task, submission.student_folder, failure);
+                    save_benchmark_failure_to_submission(submission, task, failure);
+                    continue;
+                },
+                Err(e) => {
+                    println!("[BENCHMARK] Error running task ({}) of ({}): {:#?}", task, submission.student_folder, e);
+                    continue;
+                },
+            };
+
+            // a reference that is too fast to measure counts as a hundredth of a second
+            let time_ratio = measurement.cpu_secs / reference.cpu_secs.max(0.01);
+            let memory_ratio = measurement.peak_rss_kb as f64 / reference.peak_rss_kb.max(1) as f64;
+            let too_slow = match task_config.max_ratio {
+                Some(max) => time_ratio > max || memory_ratio > max,
+                None => false,
+            };
+            save_benchmark_to_submission(submission, task, BenchmarkResult {
+                cpu_secs: measurement.cpu_secs,
+                peak_rss_kb: measurement.peak_rss_kb,
+                time_ratio,
+                memory_ratio,
+                too_slow,
+            });
+        }
+    }
+}
+
+fn measure_reference(name: &str, task_config: &TaskConfig, config: &AssignmentConfig) -> Option<Measurement> {
+    if task_config.benchmark_inputs.is_empty() {
+        return None;
+    }
+    // built and tried on every input when loading the config
+    let command = config.instructor_programs.get(task_config.reference.as_ref()?)?;
+
+    let time_file = format!("./rp_workspace/run/{}.time", workspace_name("reference", name, "benchmark"));
+    // without a reference measurement the task is not benchmarked
+    match measure_inputs(command, task_config, &time_file, &config.run_limits) {
+        Ok(BenchmarkRun::Measured(m)) => Some(m),
+        Ok(_) => {
+            println!("[BENCHMARK] Reference solution of ({}) did not finish every benchmark run, skipping benchmarks", name);
+            None
+        },
+        Err(e) => {
+            println!("[BENCHMARK] Error running reference solution of ({}), skipping benchmarks: {:#?}", name, e);
+            None
+        },
+    }
+}
+
+// medians of the repeated runs, or the first run that did not finish successfully
+fn measure_inputs(command: &str, task_config: &TaskConfig, time_file: &str, limits: &ResourceLimits) -> Result<BenchmarkRun, Error> {
+    let mut total = Measurement { cpu_secs: 0.0, peak_rss_kb: 0 };
+    for input in task_config.benchmark_inputs.iter() {
+        let mut cpu_secs = vec![];
+        let mut peak_rss_kb = vec![];
+        for _ in 0..task_config.benchmark_runs {
+            let run = match measure(command, input, time_file, limits)? {
+                BenchmarkRun::Measured(r) => r,
+                failed => return Ok(failed),
+            };
+            cpu_secs.push(run.cpu_secs);
+            peak_rss_kb.push(run.peak_rss_kb);
+        }
+        total.cpu_secs += median(&mut cpu_secs);
+        total.peak_rss_kb = total.peak_rss_kb.max(median(&mut peak_rss_kb));
+    }
+    Ok(BenchmarkRun::Measured(total))
+}
+
+fn measure(command: &str, input: &str, time_file: &str, limits: &ResourceLimits) -> Result<BenchmarkRun, Error> {
+    let _ = fs::remove_file(time_file);
+    let output = run_limited_command(&format!(
+        "/usr/bin/time -f \"%U %S %M\" -o {} {} < {} > /dev/null",
+        escape(time_file),
+        command,
+        escape(input)
+    ), limits)?;
+    if output.timed_out {
+        return Ok(BenchmarkRun::TimedOut(input.to_string()));
+    }
+    // GNU time exits with the status of the program
+    if output.status != Some(0) {
+        return Ok(BenchmarkRun::Crashed(input.to_string(), output.status));
+    }
+
+    // GNU time puts a note about the exit status before the format line
+    let content = fs::read_to_string(time_file)?;
+    let values: Vec<&str> = content.lines().last().unwrap_or("").split_whitespace().collect();
+    match values[..] {
+        [user, system, rss] => match (user.parse::<f64>(), system.parse::<f64>(), rss.parse::<u64>()) {
+            (Ok(user), Ok(system), Ok(rss)) => Ok(BenchmarkRun::Measured(Measurement { cpu_secs: user + system, peak_rss_kb: rss })),
+            _ => Err(Error::new(ErrorKind::InvalidData, format!("Unexpected time output: {}", content))),
+        },
+        _ => Err(Error::new(ErrorKind::InvalidData, format!("Unexpected time output: {}", content))),
+    }
+}
+
+fn save_benchmark_failure_to_submission(submission: &mut StudentProjectSubmission, task: &str, failure: String) {
+    if let Some(task1) = submission.has_task1.clone() {
+        if task1 == task {
+            submission.benchmark_failure_task1 = Some(failure);
+            return;
+        }
+    }
+
+    if let Some(task2) = submission.has_task2.clone() {
+        if task2 == task {
+            submission.benchmark_failure_task2 = Some(failure);
+        }
+    }
+}
+
+fn save_benchmark_to_submission(submission: &mut StudentProjectSubmission, task: &str, result: BenchmarkResult) {
+    if let Some(task1) = submission.has_task1.clone() {
+        if task1 == task {
+            submission.benchmark_task1 = Some(result);
+            return;
+        }
+    }
+
+    if let Some(task2) = submission.has_task2.clone() {
+        if task2 == task {
+            submission.benchmark_task2 = Some(result);
+        }
+    }
+}
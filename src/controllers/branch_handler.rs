@@ -0,0 +1,133 @@
+use std::io::Error;
+
+use crate::models::{student_project::StudentProjectSubmission, config::AssignmentConfig, branch_report::BranchReport};
+
+use super::{
+    validator::check_dir_exists,
+    git_commit_handler::{ACCEPTED_FOLDER_NAMES_TASK1, ACCEPTED_FOLDER_NAMES_TASK2},
+    os_helper::run_command,
+    parser::escape,
+};
+
+
+// lists the branches and tags of every repo and checks out the one to judge:
+// the configured ref, the branch with the latest commit touching a task
+// folder, or the default branch git clone checked out
+pub fn select_branches(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[BRANCHES] Error reading repos directory!");
+        std::process::exit(1);
+    }
+
+    for submission in submissions.iter_mut() {
+        // jordan's submissions share one repo
+        if !submission.cloned || submission.jordan {
+            continue;
+        }
+        let repo = escape(&submission.student_folder);
+
+        let (default_branch, branches, tags) = match list_refs(&repo) {
+            Ok(r) => r,
+            Err(e) => {
+                println!("[BRANCHES] Error listing branches of repo ({}): {:#?}", submission.student_folder, e);
+                continue;
+            },
+        };
+        let mut report = BranchReport {
+            branches,
+            tags,
+            judged: default_branch.clone(),
+            missing_ref: None,
+        };
+
+        let wanted = if let Some(git_ref) = &config.git.git_ref {
+            if report.branches.contains(git_ref) || report.tags.contains(git_ref) {
+                Some(git_ref.clone())
+            } else {
+                println!("[BRANCHES] Repo ({}) has no branch or tag ({}), judging ({})", submission.student_folder, git_ref, default_branch);
+                report.missing_ref = Some(git_ref.clone());
+                None
+            }
+        } else if config.git.all_branches {
+            latest_task_branch(&repo, &report.branches)
+        } else {
+            None
+        };
+
+        if let Some(wanted) = wanted.filter(|w| *w != default_branch) {
+            // a remote branch is checked out as a local tracking branch, a tag detached
+            match run_command(&format!("git -C ./rp_workspace/repos/{} checkout {}", repo, escape(&wanted))) {
+                Ok(_) => report.judged = wanted,
+                Err(e) => println!("[BRANCHES] Error checking out ({}) of repo ({}): {:#?}", wanted, submission.student_folder, e),
+            }
+        }
+        submission.branch_report = Some(report);
+    }
+}
+
+// (default branch, remote branches, tags)
+fn list_refs(repo: &str) -> Result<(String, Vec<String>, Vec<String>), Error> {
+    let default_branch = run_command(&format!("git -C ./rp_workspace/repos/{} rev-parse --abbrev-ref HEAD", repo))?
+        .trim()
+        .to_string();
+    let branches = run_command(&format!(
+        "git -C ./rp_workspace/repos/{} branch -r --format=\"%(refname:short)\"",
+        repo
+    ))?
+        .lines()
+        .filter_map(|b| b.trim().strip_prefix("origin/"))
+        .filter(|b| *b != "HEAD")
+        .map(|b| b.to_string())
+        .collect();
+    let tags = run_command(&format!("git -C ./rp_workspace/repos/{} tag", repo))?
+        .lines()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+    Ok((default_branch, branches, tags))
+}
+
+// branch whose newest commit touching a task folder is the latest, None if
+// no branch has a task folder
+fn latest_task_branch(repo: &str, branches: &[String]) -> Option<String> {
+    let mut latest: Option<(i64, &String)> = None;
+    for branch in branches.iter() {
+        let remote = escape(&format!("origin/{}", branch));
+        let folders: Vec<String> = match run_command(&format!(
+            "git -C ./rp_workspace/repos/{} ls-tree -r -d --name-only {}",
+            repo,
+            remote
+        )) {
+            Ok(o) => o
+                .lines()
+                .filter(|dir| {
+                    let name = dir.rsplit('/').next().unwrap_or_default();
+                    ACCEPTED_FOLDER_NAMES_TASK1.contains(&name) || ACCEPTED_FOLDER_NAMES_TASK2.contains(&name)
+                })
+                .map(escape)
+                .collect(),
+            Err(e) => {
+                println!("[BRANCHES] Error listing folders of branch ({}) of repo ({}): {:#?}", branch, repo, e);
+                continue;
+            },
+        };
+        if folders.is_empty() {
+            continue;
+        }
+
+        let time = run_command(&format!(
+            "git -C ./rp_workspace/repos/{} --no-pager log -1 --format=%ct {} -- {}",
+            repo,
+            remote,
+            folders.join(" ")
+        ))
+            .ok()
+            .and_then(|t| t.trim().parse::<i64>().ok());
+        if let Some(time) = time {
+            if latest.map(|(t, _)| time > t).unwrap_or(true) {
+                latest = Some((time, branch));
+            }
+        }
+    }
+    latest.map(|(_, branch)| branch.clone())
+}
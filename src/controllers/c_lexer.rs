@@ -0,0 +1,294 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    Identifier,
+    Keyword,
+    Number,
+    StringLiteral,
+    CharLiteral,
+    Punctuator,
+    // whole preprocessor line, e.g. "#include <stdio.h>"
+    Directive,
+    Comment,
+}
+
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct FunctionDefinition {
+    pub name: String,
+    pub return_type: String,
+    // parameter types without the parameter names
+    pub params: Vec<String>,
+    pub start_line: usize,
+    pub end_line: usize,
+    // token indices of the opening and closing brace of the body
+    pub body_start: usize,
+    pub body_end: usize,
+}
+
+const KEYWORDS: [&str; 44] = [
+    "auto", "break", "case", "char", "const", "continue", "default", "do", "double", "else", "enum",
+    "extern", "float", "for", "goto", "if", "inline", "int", "long", "register", "restrict", "return",
+    "short", "signed", "sizeof", "static", "struct", "switch", "typedef", "union", "unsigned", "void",
+    "volatile", "while", "_Alignas", "_Alignof", "_Atomic", "_Bool", "_Complex", "_Generic",
+    "_Imaginary", "_Noreturn", "_Static_assert", "_Thread_local",
+];
+
+// longest first, so the greedy match picks "<<=" over "<<" over "<"
+const PUNCTUATORS: [&str; 22] = [
+    "<<=", ">>=", "...", "->", "++", "--", "<<", ">>", "<=", ">=", "==", "!=", "&&", "||",
+    "*=", "/=", "%=", "+=", "-=", "&=", "^=", "|=",
+];
+
+pub fn tokenize(source: &str) -> Vec<Token> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = vec![];
+    let mut line = 1;
+    // only whitespace since the last newline, so '#' starts a directive
+    let mut line_start = true;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start_line = line;
+
+        if c == '\n' {
+            line += 1;
+            line_start = true;
+            i += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        // preprocessor directive, including backslash continued lines
+        if c == '#' && line_start {
+            let start = i;
+            while i < chars.len() && chars[i] != '\n' {
+                if chars[i] == '\\' && i + 1 < chars.len() && chars[i + 1] == '\n' {
+                    line += 1;
+                    i += 1;
+                }
+                i += 1;
+            }
+            tokens.push(Token { kind: TokenKind::Directive, text: chars[start..i].iter().collect(), line: start_line });
+            continue;
+        }
+        line_start = false;
+
+        // comments
+        if c == '/' && i + 1 < chars.len() && chars[i + 1] == '/' {
+            let start = i;
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            tokens.push(Token { kind: TokenKind::Comment, text: chars[start..i].iter().collect(), line: start_line });
+            continue;
+        }
+        if c == '/' && i + 1 < chars.len() && chars[i + 1] == '*' {
+            let start = i;
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && i + 1 < chars.len() && chars[i + 1] == '/') {
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+            i = (i + 2).min(chars.len());
+            tokens.push(Token { kind: TokenKind::Comment, text: chars[start..i].iter().collect(), line: start_line });
+            continue;
+        }
+
+        // string and character literals
+        if c == '"' || c == '\'' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != c && chars[i] != '\n' {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i = (i + 1).min(chars.len());
+            let kind = if c == '"' { TokenKind::StringLiteral } else { TokenKind::CharLiteral };
+            tokens.push(Token { kind, text: chars[start..i].iter().collect(), line: start_line });
+            continue;
+        }
+
+        if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let kind = if KEYWORDS.contains(&text.as_str()) { TokenKind::Keyword } else { TokenKind::Identifier };
+            tokens.push(Token { kind, text, line: start_line });
+            continue;
+        }
+
+        if c.is_ascii_digit() || (c == '.' && i + 1 < chars.len() && chars[i + 1].is_ascii_digit()) {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '.' || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token { kind: TokenKind::Number, text: chars[start..i].iter().collect(), line: start_line });
+            continue;
+        }
+
+        let rest: String = chars[i..(i + 3).min(chars.len())].iter().collect();
+        let text = match PUNCTUATORS.iter().find(|p| rest.starts_with(*p)) {
+            Some(p) => p.to_string(),
+            None => c.to_string(),
+        };
+        i += text.chars().count();
+        tokens.push(Token { kind: TokenKind::Punctuator, text, line: start_line });
+    }
+    tokens
+}
+
+// tokens without comments and directives, the input of find_functions
+pub fn code_tokens(tokens: &[Token]) -> Vec<Token> {
+    tokens
+        .iter()
+        .filter(|t| t.kind != TokenKind::Comment && t.kind != TokenKind::Directive)
+        .cloned()
+        .collect()
+}
+
+// finds function definitions on the top level: "type name(params) { ... }"
+pub fn find_functions(tokens: &[Token]) -> Vec<FunctionDefinition> {
+    let mut functions = vec![];
+    let mut depth = 0;
+    // index of the first token of the current top level declaration
+    let mut declaration_start = 0;
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let text = tokens[i].text.as_str();
+        if depth > 0 {
+            match text {
+                "{" => depth += 1,
+                "}" => {
+                    depth -= 1;
+                    if depth == 0 {
+                        declaration_start = i + 1;
+                    }
+                },
+                _ => (),
+            }
+            i += 1;
+            continue;
+        }
+
+        match text {
+            ";" => declaration_start = i + 1,
+            "{" => depth += 1,
+            "(" if i > 0 && tokens[i - 1].kind == TokenKind::Identifier => {
+                let close = match matching_paren(tokens, i) {
+                    Some(c) => c,
+                    None => break,
+                };
+                if close + 1 < tokens.len() && tokens[close + 1].text == "{" {
+                    let body_end = matching_brace(tokens, close + 1).unwrap_or(tokens.len() - 1);
+                    functions.push(FunctionDefinition {
+                        name: tokens[i - 1].text.clone(),
+                        return_type: join_tokens(&tokens[declaration_start..i - 1]),
+                        params: split_params(&tokens[i + 1..close]),
+                        start_line: tokens[declaration_start.min(i - 1)].line,
+                        end_line: tokens[body_end].line,
+                        body_start: close + 1,
+                        body_end,
+                    });
+                    i = body_end + 1;
+                    declaration_start = i;
+                    continue;
+                }
+                i = close;
+            },
+            _ => (),
+        }
+        i += 1;
+    }
+    functions
+}
+
+fn matching_paren(tokens: &[Token], open: usize) -> Option<usize> {
+    matching(tokens, open, "(", ")")
+}
+
+fn matching_brace(tokens: &[Token], open: usize) -> Option<usize> {
+    matching(tokens, open, "{", "}")
+}
+
+fn matching(tokens: &[Token], open: usize, open_text: &str, close_text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        if token.text == open_text {
+            depth += 1;
+        } else if token.text == close_text {
+            depth -= 1;
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+    }
+    None
+}
+
+// "int a, char *b" => ["int", "char *"], "void" and "" => []
+fn split_params(tokens: &[Token]) -> Vec<String> {
+    let mut params = vec![];
+    let mut depth = 0;
+    let mut current: Vec<&Token> = vec![];
+    for token in tokens.iter() {
+        match token.text.as_str() {
+            "(" | "[" => depth += 1,
+            ")" | "]" => depth -= 1,
+            "," if depth == 0 => {
+                params.push(param_type(&current));
+                current = vec![];
+                continue;
+            },
+            _ => (),
+        }
+        current.push(token);
+    }
+    if !current.is_empty() {
+        params.push(param_type(&current));
+    }
+    if params.len() == 1 && params[0] == "void" {
+        return vec![];
+    }
+    params
+}
+
+fn param_type(tokens: &[&Token]) -> String {
+    // drop the trailing array brackets and the parameter name
+    let mut end = tokens.len();
+    if let Some(bracket) = tokens.iter().position(|t| t.text == "[") {
+        end = bracket;
+    }
+    if end > 1 && tokens[end - 1].kind == TokenKind::Identifier {
+        end -= 1;
+    }
+    tokens[..end]
+        .iter()
+        .map(|t| t.text.as_str())
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+pub fn join_tokens(tokens: &[Token]) -> String {
+    tokens
+        .iter()
+        .map(|t| t.text.as_str())
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
@@ -0,0 +1,171 @@
+use chrono::{DateTime, FixedOffset};
+
+use crate::models::{student_project::StudentProjectSubmission, config::{AssignmentConfig, CommitQualityConfig}, commit_quality::CommitQuality};
+
+use super::{
+    validator::{check_dir_exists, tasks_to_check},
+    git_commit_handler::get_commits_from_submission,
+    stats::median,
+    os_helper::run_command,
+    parser::escape,
+};
+
+
+struct TaskCommit {
+    hash: String,
+    date: DateTime<FixedOffset>,
+    subject: String,
+    added: usize,
+    deleted: usize,
+}
+
+pub fn check_commit_quality(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[COMMIT QUALITY] Error reading repos directory!");
+        std::process::exit(1);
+    }
+
+    for submission in submissions.iter_mut() {
+        if !submission.cloned {
+            continue;
+        }
+
+        for task in tasks_to_check(submission).iter() {
+            // the commit list may be cut at the deadline
+            let hashes = match get_commits_from_submission(task, submission) {
+                Some(c) if !c.is_empty() => c,
+                _ => continue,
+            };
+            let commits = match read_task_commits(&submission.student_folder, task) {
+                Ok(c) => c.into_iter().filter(|c| hashes.contains(&c.hash)).collect::<Vec<_>>(),
+                Err(e) => {
+                    println!("[COMMIT QUALITY] Error reading the log of task ({}) of ({}): {:#?}", task, submission.student_folder, e);
+                    continue;
+                },
+            };
+            if commits.is_empty() {
+                continue;
+            }
+            let quality = rate_commits(&commits, &config.commit_quality);
+            save_commit_quality_to_submission(submission, task, quality);
+        }
+    }
+}
+
+// commits touching the task with the lines they changed inside of it, newest first
+fn read_task_commits(student_folder: &str, task: &str) -> Result<Vec<TaskCommit>, std::io::Error> {
+    // every commit starts with 0x1e, followed by its numstat lines
+    let output = run_command(&format!(
+        "git -C ./rp_workspace/repos/{} --no-pager log --format=\"%x1e%h%x1f%cI%x1f%s\" --numstat -- {}",
+        escape(student_folder),
+        escape(task)
+    ))?;
+
+    let mut commits = vec![];
+    for record in output.split('\x1e').filter(|r| !r.trim().is_empty()) {
+        let mut lines = record.lines();
+        let fields: Vec<&str> = lines.next().unwrap_or_default().split('\x1f').collect();
+        if fields.len() != 3 {
+            continue;
+        }
+        let date = match DateTime::parse_from_rfc3339(fields[1]) {
+            Ok(d) => d,
+            Err(e) => {
+                println!("[COMMIT QUALITY] Error parsing date of commit ({}): {:#?}", fields[0], e);
+                continue;
+            },
+        };
+
+        let mut commit = TaskCommit {
+            hash: fields[0].to_string(),
+            date,
+            subject: fields[2].trim().to_string(),
+            added: 0,
+            deleted: 0,
+        };
+        // "added<TAB>deleted<TAB>path", binary files have "-" for both
+        for line in lines {
+            let mut counts = line.split('\t');
+            if let (Some(Ok(added)), Some(Ok(deleted))) = (
+                counts.next().map(|c| c.parse::<usize>()),
+                counts.next().map(|c| c.parse::<usize>()),
+            ) {
+                commit.added += added;
+                commit.deleted += deleted;
+            }
+        }
+        commits.push(commit);
+    }
+    Ok(commits)
+}
+
+fn rate_commits(commits: &[TaskCommit], config: &CommitQualityConfig) -> CommitQuality {
+    let mut quality = CommitQuality {
+        commits: commits.len(),
+        ..Default::default()
+    };
+
+    let mut message_lengths: Vec<usize> = commits.iter().map(|c| c.subject.chars().count()).collect();
+    quality.median_message_length = median(&mut message_lengths);
+    quality.default_messages = commits
+        .iter()
+        .filter(|c| c.subject.is_empty() || config.default_messages.contains(&c.subject.to_lowercase()))
+        .count();
+    quality.short_messages = message_lengths.iter().filter(|l| **l < config.min_message_length).count();
+    // default messages are usually short as well, count each bad message once
+    let bad_messages = commits
+        .iter()
+        .filter(|c| c.subject.chars().count() < config.min_message_length || config.default_messages.contains(&c.subject.to_lowercase()))
+        .count();
+
+    let mut lines_changed: Vec<usize> = commits.iter().map(|c| c.added + c.deleted).collect();
+    quality.median_lines_changed = median(&mut lines_changed);
+    let total_added: usize = commits.iter().map(|c| c.added).sum();
+    if let Some(largest) = commits.iter().max_by_key(|c| c.added) {
+        if total_added > 0 {
+            quality.largest_commit_share = largest.added as f64 / total_added as f64 * 100.0;
+        }
+        if quality.largest_commit_share > config.big_bang_share {
+            quality.big_bang_commit = Some(largest.hash.clone());
+        }
+    }
+
+    let first = commits.iter().map(|c| c.date).min();
+    let last = commits.iter().map(|c| c.date).max();
+    if let (Some(first), Some(last)) = (first, last) {
+        quality.spread_days = (last - first).num_seconds() as f64 / 86_400.0;
+    }
+
+    // each part is between 0 and 1
+    let messages = 1.0 - bad_messages as f64 / commits.len() as f64;
+    let granularity = if quality.big_bang_commit.is_some() {
+        0.0
+    } else {
+        (commits.len() as f64 / config.min_commits.max(1) as f64).min(1.0)
+    };
+    let spread = if config.min_spread_days > 0.0 {
+        (quality.spread_days / config.min_spread_days).min(1.0)
+    } else {
+        1.0
+    };
+    let weights = config.message_weight + config.granularity_weight + config.spread_weight;
+    quality.score = 100.0
+        * (config.message_weight * messages + config.granularity_weight * granularity + config.spread_weight * spread)
+        / weights;
+    quality
+}
+
+fn save_commit_quality_to_submission(submission: &mut StudentProjectSubmission, task: &str, quality: CommitQuality) {
+    if let Some(task1) = submission.has_task1.clone() {
+        if task1 == task {
+            submission.commit_quality_task1 = Some(quality);
+            return;
+        }
+    }
+
+    if let Some(task2) = submission.has_task2.clone() {
+        if task2 == task {
+            submission.commit_quality_task2 = Some(quality);
+        }
+    }
+}
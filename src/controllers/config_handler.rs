@@ -0,0 +1,572 @@
+use std::{fs, path::Path};
+
+use chrono::{FixedOffset, NaiveDate, NaiveDateTime};
+
+use crate::models::{config::{AssignmentConfig, ResourceLimits, RosterEntry, TaskConfig}, file_path::FilePath, student_project::StudentProjectSubmission, test_case::{TestCase, CompareMode}};
+
+use super::{
+    languages::{get_language, Language, c::C},
+    os_helper::run_limited_command,
+    test_handler::instructor_program_command,
+    parser::escape,
+};
+
+pub struct ConfigEntry {
+    pub section: String,
+    pub key: String,
+    pub value: String,
+    pub line: usize,
+}
+
+pub fn load_config(sources: &[FilePath]) -> AssignmentConfig {
+    let mut config = AssignmentConfig::default();
+
+    for source in sources.iter() {
+        let path = match source {
+            FilePath::Config(path) => path,
+            _ => continue,
+        };
+
+        let content = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(e) => {
+                println!("[CONFIG] Error reading config file ({}):\n{:#?}", path, e);
+                std::process::exit(1);
+            }
+        };
+
+        for entry in read_entries(&content).iter() {
+            if let Err(e) = apply_entry(&mut config, entry) {
+                println!("[CONFIG] Error in config file ({}) on line {}: {}", path, entry.line, e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if let Err(e) = validate_config(&config) {
+        println!("[CONFIG] Error in config: {}", e);
+        std::process::exit(1);
+    }
+    if let Err(e) = prepare_instructor_programs(&mut config) {
+        println!("[CONFIG] Error in instructor program: {}", e);
+        std::process::exit(1);
+    }
+    config
+}
+
+// builds and tries the instructor programs before any student work starts,
+// so a broken one stops the judge before it is needed
+fn prepare_instructor_programs(config: &mut AssignmentConfig) -> Result<(), String> {
+    for (name, task) in [("task1", config.task1.clone()), ("task2", config.task2.clone())] {
+        if let Some(generator) = &task.generator {
+            let command = build_instructor_program(config, generator)?;
+            let run = run_limited_command(&format!("{} {}", command, task.random_seed), &config.run_limits)
+                .map_err(|e| format!("Error running generator of [{}] ({}): {}", name, generator, e))?;
+            if run.timed_out || run.status != Some(0) {
+                return Err(format!("Generator of [{}] ({}) failed for seed {}: {}", name, generator, task.random_seed, run.stderr));
+            }
+        }
+        for program in [&task.reference, &task.validator].into_iter().flatten() {
+            build_instructor_program(config, program)?;
+        }
+        for checker in get_task_tests(&task).into_iter().filter_map(|t| t.checker) {
+            build_instructor_program(config, &checker)?;
+        }
+        // validate_config makes sure benchmarks have a reference
+        if let (Some(reference), false) = (&task.reference, task.benchmark_inputs.is_empty()) {
+            if !Path::new("/usr/bin/time").is_file() {
+                return Err("benchmarks need GNU time at /usr/bin/time".to_string());
+            }
+            let command = build_instructor_program(config, reference)?;
+            for input in task.benchmark_inputs.iter() {
+                if !Path::new(input).is_file() {
+                    return Err(format!("Benchmark input of [{}] ({}) does not exist", name, input));
+                }
+                let run = run_limited_command(&format!("{} < {} > /dev/null", command, escape(input)), &config.run_limits)
+                    .map_err(|e| format!("Error running reference solution of [{}] on ({}): {}", name, input, e))?;
+                if run.timed_out || run.status != Some(0) {
+                    return Err(format!("Reference solution of [{}] did not finish on ({}), raise [limits.run] for large inputs", name, input));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// command of the program, built once per path
+fn build_instructor_program(config: &mut AssignmentConfig, path: &str) -> Result<String, String> {
+    if let Some(command) = config.instructor_programs.get(path) {
+        return Ok(command.clone());
+    }
+    if !Path::new(path).is_file() {
+        return Err(format!("({}) does not exist", path));
+    }
+    let command = instructor_program_command(path, &config.compile_limits)
+        .map_err(|e| format!("Error building ({}): {}", path, e))?;
+    config.instructor_programs.insert(path.to_string(), command.clone());
+    Ok(command)
+}
+
+// settings that only make sense together
+fn validate_config(config: &AssignmentConfig) -> Result<(), String> {
+    for (name, task) in [("task1", &config.task1), ("task2", &config.task2)] {
+        if task.generator.is_some() && task.reference.is_none() {
+            return Err(format!("[{}] has a generator but no reference solution", name));
+        }
+        // a shrunk input is only shown to the student if it is known to be valid
+        if task.generator.is_some() && task.shrink && task.validator.is_none() {
+            return Err(format!("[{}] shrinks failing inputs but has no validator", name));
+        }
+        if !task.benchmark_inputs.is_empty() && task.reference.is_none() {
+            return Err(format!("[{}] has benchmark inputs but no reference solution", name));
+        }
+        if has_stray_tolerance(task.compare.as_ref(), task.tolerance) {
+            return Err(format!("[{}] sets a tolerance but does not compare as float", name));
+        }
+        for test in task.tests.iter() {
+            if has_stray_tolerance(test.compare.as_ref(), test.tolerance) {
+                return Err(format!("[{}.test.{}] sets a tolerance but does not compare as float", name, test.name));
+            }
+        }
+    }
+    if config.git.git_ref.is_some() && config.git.all_branches {
+        return Err("[git] sets both a ref and all_branches".to_string());
+    }
+    let quality = &config.commit_quality;
+    if quality.message_weight + quality.granularity_weight + quality.spread_weight <= 0.0 {
+        return Err("[commit_quality] weights must add up to more than 0".to_string());
+    }
+    if config.judge_at_deadline && config.task1.deadline.is_none() && config.task2.deadline.is_none() {
+        return Err("judge_at_deadline is set but no task has a deadline".to_string());
+    }
+    Ok(())
+}
+
+// a tolerance only applies to a float comparison
+fn has_stray_tolerance(compare: Option<&CompareMode>, tolerance: Option<f64>) -> bool {
+    tolerance.is_some() && !matches!(compare, None | Some(CompareMode::Float(_)))
+}
+
+// reads a simple ini file: "[section]" headers followed by "key = value" lines,
+// lines starting with '#' or ';' are comments
+pub fn read_entries(content: &str) -> Vec<ConfigEntry> {
+    let mut entries = vec![];
+    let mut section = "".to_string();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if line.starts_with('[') && line.ends_with(']') {
+            // matched case-insensitively, test names keep their case
+            section = line[1..line.len() - 1].trim().to_string();
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once('=') {
+            entries.push(ConfigEntry {
+                section: section.clone(),
+                key: key.trim().to_lowercase(),
+                value: value.trim().to_string(),
+                line: i + 1,
+            });
+        }
+    }
+    entries
+}
+
+fn apply_entry(config: &mut AssignmentConfig, entry: &ConfigEntry) -> Result<(), String> {
+    match entry.section.to_lowercase().as_str() {
+        "limits.clone" => apply_limit(&mut config.clone_limits, entry),
+        "limits.compile" => apply_limit(&mut config.compile_limits, entry),
+        "limits.run" => apply_limit(&mut config.run_limits, entry),
+        "task1" => apply_task(&mut config.task1, entry),
+        "task2" => apply_task(&mut config.task2, entry),
+        "stages" => apply_stage(config, entry),
+        "style" => apply_style(config, entry),
+        "timestamps" => apply_timestamps(config, entry),
+        "authorship" => apply_authorship(config, entry),
+        "commit_quality" => apply_commit_quality(config, entry),
+        "timeline" => apply_timeline(config, entry),
+        "git" => apply_git(config, entry),
+        "shared_history" => apply_shared_history(config, entry),
+        "hygiene" => apply_hygiene(config, entry),
+        section if section.starts_with("task1.test.") => apply_test(&mut config.task1, &entry.section["task1.test.".len()..], entry),
+        section if section.starts_with("task2.test.") => apply_test(&mut config.task2, &entry.section["task2.test.".len()..], entry),
+        _ => Err(format!("Unknown section [{}]", entry.section)),
+    }
+}
+
+fn apply_stage(config: &mut AssignmentConfig, entry: &ConfigEntry) -> Result<(), String> {
+    match entry.key.as_str() {
+        "sanitizer" => config.sanitizer = parse_bool(entry)?,
+        "valgrind" => config.valgrind = parse_bool(entry)?,
+        "test_history" => config.test_history = parse_bool(entry)?,
+        "coverage" => config.coverage = parse_bool(entry)?,
+        "metrics_history" => config.metrics_history = parse_bool(entry)?,
+        "style" => config.style = parse_bool(entry)?,
+        "judge_at_deadline" => config.judge_at_deadline = parse_bool(entry)?,
+        _ => return Err(format!("Unknown stage ({})", entry.key)),
+    };
+    Ok(())
+}
+
+fn apply_style(config: &mut AssignmentConfig, entry: &ConfigEntry) -> Result<(), String> {
+    match entry.key.as_str() {
+        "clang_format" => config.style_config.clang_format = Some(entry.value.to_string()),
+        "max_line_length" => config.style_config.max_line_length = parse_number(entry)?,
+        _ => return Err(format!("Unknown key ({}) in section [{}]", entry.key, entry.section)),
+    };
+    Ok(())
+}
+
+fn apply_timestamps(config: &mut AssignmentConfig, entry: &ConfigEntry) -> Result<(), String> {
+    match entry.key.as_str() {
+        "max_author_gap_hours" => config.timestamp_config.max_author_gap_hours = parse_number(entry)?,
+        "max_identical" => config.timestamp_config.max_identical = parse_number(entry)?,
+        "max_timezone_jump_hours" => config.timestamp_config.max_timezone_jump_hours = parse_number(entry)?,
+        _ => return Err(format!("Unknown key ({}) in section [{}]", entry.key, entry.section)),
+    };
+    Ok(())
+}
+
+fn apply_commit_quality(config: &mut AssignmentConfig, entry: &ConfigEntry) -> Result<(), String> {
+    let quality = &mut config.commit_quality;
+    match entry.key.as_str() {
+        "default_messages" => quality.default_messages = parse_list(entry).iter().map(|m| m.to_lowercase()).collect(),
+        "min_message_length" => quality.min_message_length = parse_number(entry)?,
+        "min_commits" => quality.min_commits = parse_number(entry)?,
+        "big_bang_share" => quality.big_bang_share = parse_number(entry)?,
+        "min_spread_days" => quality.min_spread_days = parse_number(entry)?,
+        "message_weight" => quality.message_weight = parse_number(entry)?,
+        "granularity_weight" => quality.granularity_weight = parse_number(entry)?,
+        "spread_weight" => quality.spread_weight = parse_number(entry)?,
+        _ => return Err(format!("Unknown key ({}) in section [{}]", entry.key, entry.section)),
+    };
+    Ok(())
+}
+
+fn apply_shared_history(config: &mut AssignmentConfig, entry: &ConfigEntry) -> Result<(), String> {
+    match entry.key.as_str() {
+        "ignore_commits" => config.ignore_shared_commits = parse_list(entry).iter().map(|h| h.to_lowercase()).collect(),
+        _ => return Err(format!("Unknown key ({}) in section [{}]", entry.key, entry.section)),
+    };
+    Ok(())
+}
+
+fn apply_hygiene(config: &mut AssignmentConfig, entry: &ConfigEntry) -> Result<(), String> {
+    let hygiene = &mut config.hygiene;
+    match entry.key.as_str() {
+        "binaries" => hygiene.binaries = parse_list(entry),
+        "ide_folders" => hygiene.ide_folders = parse_list(entry),
+        "junk" => hygiene.junk = parse_list(entry),
+        "detect_executables" => hygiene.detect_executables = parse_bool(entry)?,
+        "max_file_kb" => hygiene.max_file_kb = parse_number(entry)?,
+        "require_gitignore" => hygiene.require_gitignore = parse_bool(entry)?,
+        "history" => hygiene.history = parse_bool(entry)?,
+        _ => return Err(format!("Unknown key ({}) in section [{}]", entry.key, entry.section)),
+    };
+    Ok(())
+}
+
+fn apply_git(config: &mut AssignmentConfig, entry: &ConfigEntry) -> Result<(), String> {
+    match entry.key.as_str() {
+        "ref" => config.git.git_ref = Some(entry.value.to_string()),
+        "all_branches" => config.git.all_branches = parse_bool(entry)?,
+        _ => return Err(format!("Unknown key ({}) in section [{}]", entry.key, entry.section)),
+    };
+    Ok(())
+}
+
+fn apply_timeline(config: &mut AssignmentConfig, entry: &ConfigEntry) -> Result<(), String> {
+    let date = NaiveDate::parse_from_str(&entry.value, "%Y-%m-%d")
+        .map_err(|_| format!("Value of {} is not a date like 2024-01-06: {}", entry.key, entry.value))?;
+    match entry.key.as_str() {
+        "start" => config.timeline.start = Some(date),
+        "end" => config.timeline.end = Some(date),
+        _ => return Err(format!("Unknown key ({}) in section [{}]", entry.key, entry.section)),
+    };
+    Ok(())
+}
+
+fn apply_authorship(config: &mut AssignmentConfig, entry: &ConfigEntry) -> Result<(), String> {
+    match entry.key.as_str() {
+        "roster" => config.roster = read_roster(&entry.value)?,
+        _ => return Err(format!("Unknown key ({}) in section [{}]", entry.key, entry.section)),
+    };
+    Ok(())
+}
+
+// "name,email,username" lines, email and username may be empty
+fn read_roster(path: &str) -> Result<Vec<RosterEntry>, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Error reading roster ({}): {}", path, e))?;
+    let mut roster = vec![];
+    for line in content.lines() {
+        let fields: Vec<&str> = line.split(',').map(|f| f.trim()).collect();
+        // skip empty lines and the header
+        if fields[0].is_empty() || fields[0].eq_ignore_ascii_case("name") {
+            continue;
+        }
+        let field = |i: usize| fields.get(i).filter(|f| !f.is_empty()).map(|f| f.to_string());
+        roster.push(RosterEntry {
+            name: fields[0].to_string(),
+            email: field(1),
+            username: field(2),
+        });
+    }
+    Ok(roster)
+}
+
+fn apply_task(task: &mut TaskConfig, entry: &ConfigEntry) -> Result<(), String> {
+    match entry.key.as_str() {
+        "language" => {
+            if get_language(&entry.value).is_none() {
+                return Err(format!("Unsupported language: {}", entry.value));
+            }
+            task.language = entry.value.to_lowercase();
+        },
+        "inputs" => task.inputs = parse_list(entry),
+        "driver" => task.driver = Some(entry.value.to_string()),
+        "tests_dir" => task.tests_dir = Some(entry.value.to_string()),
+        "compare" => task.compare = Some(parse_compare_mode(&entry.value)?),
+        "tolerance" => task.tolerance = Some(parse_number(entry)?),
+        "checker" => task.checker = Some(entry.value.to_string()),
+        "reference" => task.reference = Some(entry.value.to_string()),
+        "generator" => task.generator = Some(entry.value.to_string()),
+        "random_tests" => task.random_tests = parse_number(entry)?,
+        "random_seed" => task.random_seed = parse_number(entry)?,
+        "shrink" => task.shrink = parse_bool(entry)?,
+        "validator" => task.validator = Some(entry.value.to_string()),
+        "benchmark_inputs" => task.benchmark_inputs = parse_list(entry),
+        "benchmark_runs" => task.benchmark_runs = match parse_number(entry)? {
+            0 => return Err("benchmark_runs must be at least 1".to_string()),
+            runs => runs,
+        },
+        "max_ratio" => task.max_ratio = Some(parse_number(entry)?),
+        "deadline" => task.deadline = Some(parse_deadline(&entry.value)?),
+        "timezone" => task.timezone = parse_timezone(&entry.value)?,
+        "grace_minutes" => task.grace_minutes = parse_number(entry)?,
+        "banned_headers" => task.rules.banned_headers = parse_list(entry),
+        "banned_functions" => task.rules.banned_functions = parse_list(entry),
+        "required_functions" => task.rules.required_functions = parse_signature_list(entry),
+        "required_keywords" => task.rules.required_keywords = parse_list(entry),
+        "require_recursion" => task.rules.require_recursion = parse_bool(entry)?,
+        "max_lines" => task.rules.max_lines = Some(parse_number(entry)?),
+        _ => return Err(format!("Unknown key ({}) in section [{}]", entry.key, entry.section)),
+    };
+    Ok(())
+}
+
+fn apply_limit(limits: &mut ResourceLimits, entry: &ConfigEntry) -> Result<(), String> {
+    match entry.key.as_str() {
+        "timeout" => limits.timeout_secs = parse_number(entry)?,
+        "memory_mb" => limits.memory_mb = match entry.value.as_str() {
+            "unlimited" => None,
+            _ => Some(parse_number(entry)?),
+        },
+        _ => return Err(format!("Unknown key ({}) in section [{}]", entry.key, entry.section)),
+    };
+    Ok(())
+}
+
+// keys of a [taskN.test.NAME] section
+fn apply_test(task: &mut TaskConfig, name: &str, entry: &ConfigEntry) -> Result<(), String> {
+    let index = match task.tests.iter().position(|t| t.name == name) {
+        Some(i) => i,
+        None => {
+            task.tests.push(TestCase::new(name.to_string()));
+            task.tests.len() - 1
+        },
+    };
+    let test = &mut task.tests[index];
+
+    match entry.key.as_str() {
+        "args" => test.args = entry.value.split_whitespace().map(|a| a.to_string()).collect(),
+        "stdin" => test.stdin = Some(entry.value.to_string()),
+        "files" => test.files = parse_list(entry),
+        "exit_code" => test.exit_code = Some(parse_number(entry)?),
+        "stdout" => test.stdout = Some(entry.value.to_string()),
+        "stderr" => test.stderr = Some(entry.value.to_string()),
+        "compare" => test.compare = Some(parse_compare_mode(&entry.value)?),
+        "tolerance" => test.tolerance = Some(parse_number(entry)?),
+        "checker" => test.checker = Some(entry.value.to_string()),
+        "weight" => test.weight = parse_number(entry)?,
+        "visibility" => test.hidden = match entry.value.to_lowercase().as_str() {
+            "public" => false,
+            "hidden" => true,
+            _ => return Err(format!("Visibility must be public or hidden: {}", entry.value)),
+        },
+        _ => return Err(format!("Unknown key ({}) in section [{}]", entry.key, entry.section)),
+    };
+    Ok(())
+}
+
+// the tolerance of a float comparison is set by its own key
+fn parse_compare_mode(value: &str) -> Result<CompareMode, String> {
+    match value.to_lowercase().as_str() {
+        "exact" => Ok(CompareMode::Exact),
+        "whitespace" => Ok(CompareMode::Whitespace),
+        "case" | "case_insensitive" => Ok(CompareMode::CaseInsensitive),
+        "regex" => Ok(CompareMode::Regex),
+        "float" => Ok(CompareMode::Float(1e-6)),
+        "unordered" | "unordered_lines" => Ok(CompareMode::UnorderedLines),
+        _ => Err(format!("Unknown compare mode: {}", value)),
+    }
+}
+
+// "2024-01-06 23:59", with optional seconds, or a date for the end of that day
+fn parse_deadline(value: &str) -> Result<NaiveDateTime, String> {
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(deadline) = NaiveDateTime::parse_from_str(value, format) {
+            return Ok(deadline);
+        }
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(23, 59, 59))
+        .ok_or(format!("Deadline is not a date like 2024-01-06 23:59: {}", value))
+}
+
+// "UTC" or an offset like "+01:00"
+fn parse_timezone(value: &str) -> Result<FixedOffset, String> {
+    if value.eq_ignore_ascii_case("utc") || value == "Z" {
+        return Ok(FixedOffset::east_opt(0).unwrap());
+    }
+    value
+        .parse::<FixedOffset>()
+        .map_err(|_| format!("Timezone is not UTC or an offset like +01:00: {}", value))
+}
+
+pub fn parse_bool(entry: &ConfigEntry) -> Result<bool, String> {
+    match entry.value.to_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Ok(true),
+        "false" | "no" | "off" | "0" => Ok(false),
+        _ => Err(format!("Value of {} is not a valid boolean: {}", entry.key, entry.value)),
+    }
+}
+
+// comma separated values, empty items are dropped
+pub fn parse_list(entry: &ConfigEntry) -> Vec<String> {
+    entry.value
+        .split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+// like parse_list, but commas inside of parentheses belong to a signature:
+// "fib, int sum(int, int)" => ["fib", "int sum(int, int)"]
+fn parse_signature_list(entry: &ConfigEntry) -> Vec<String> {
+    let mut items = vec![];
+    let mut current = String::new();
+    let mut depth = 0;
+    for c in entry.value.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(current.trim().to_string());
+                current = String::new();
+                continue;
+            },
+            _ => (),
+        }
+        current.push(c);
+    }
+    items.push(current.trim().to_string());
+    items.into_iter().filter(|item| !item.is_empty()).collect()
+}
+
+pub fn parse_number<T: std::str::FromStr>(entry: &ConfigEntry) -> Result<T, String> {
+    entry.value
+        .parse::<T>()
+        .map_err(|_| format!("Value of {} is not a valid number: {}", entry.key, entry.value))
+}
+
+pub fn get_task_config<'a>(config: &'a AssignmentConfig, submission: &StudentProjectSubmission, task: &str) -> Option<&'a TaskConfig> {
+    if let Some(task1) = &submission.has_task1 {
+        if task1 == task {
+            return Some(&config.task1);
+        }
+    }
+
+    if let Some(task2) = &submission.has_task2 {
+        if task2 == task {
+            return Some(&config.task2);
+        }
+    }
+    None
+}
+
+// language of the task, C if the task has no config
+pub fn get_task_language(config: &AssignmentConfig, submission: &StudentProjectSubmission, task: &str) -> Box<dyn Language> {
+    get_task_config(config, submission, task)
+        .and_then(|task_config| get_language(&task_config.language))
+        .unwrap_or_else(|| Box::new(C))
+}
+
+// stdin files to run the task with, a single run without input if there are none
+pub fn get_task_inputs(task_config: &TaskConfig) -> Vec<Option<String>> {
+    if task_config.inputs.is_empty() {
+        return vec![None];
+    }
+    task_config.inputs
+        .iter()
+        .map(|input| Some(input.to_string()))
+        .collect()
+}
+
+// configured test cases followed by the cases found in the tests folder
+pub fn get_task_tests(task_config: &TaskConfig) -> Vec<TestCase> {
+    let mut tests = task_config.tests.clone();
+    for test in tests.iter_mut() {
+        // a case that sets either key takes neither from the task
+        if test.compare.is_none() && test.tolerance.is_none() {
+            test.compare = task_config.compare.clone();
+            test.tolerance = task_config.tolerance;
+        }
+        if test.checker.is_none() {
+            test.checker = task_config.checker.clone();
+        }
+    }
+    let folder = match &task_config.tests_dir {
+        Some(f) => f,
+        None => return tests,
+    };
+
+    let mut inputs: Vec<String> = match fs::read_dir(folder) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| name.ends_with(".in"))
+            .collect(),
+        Err(e) => {
+            println!("[CONFIG] Error reading tests directory ({}): {:#?}", folder, e);
+            return tests;
+        },
+    };
+    inputs.sort();
+
+    for input in inputs.iter() {
+        let name = input.trim_end_matches(".in");
+        let mut test = TestCase::new(name.to_string());
+        test.compare = task_config.compare.clone();
+        test.tolerance = task_config.tolerance;
+        test.checker = task_config.checker.clone();
+        test.stdin = Some(format!("{}/{}", folder, input));
+        let stdout = format!("{}/{}.out", folder, name);
+        if Path::new(&stdout).is_file() {
+            test.stdout = Some(stdout);
+        }
+        let stderr = format!("{}/{}.err", folder, name);
+        if Path::new(&stderr).is_file() {
+            test.stderr = Some(stderr);
+        }
+        if let Ok(args) = fs::read_to_string(format!("{}/{}.args", folder, name)) {
+            test.args = args.split_whitespace().map(|a| a.to_string()).collect();
+        }
+        tests.push(test);
+    }
+    tests
+}
\ No newline at end of file
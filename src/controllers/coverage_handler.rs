@@ -0,0 +1,212 @@
+use std::{fs, io::ErrorKind};
+
+use regex::Regex;
+
+use crate::models::{student_project::StudentProjectSubmission, config::AssignmentConfig, coverage_report::CoverageReport};
+
+use super::{
+    validator::{check_dir_exists, tasks_to_check},
+    config_handler::{get_task_config, get_task_inputs, get_task_language, get_task_tests},
+    git_compilation_handler::{build_task, get_submission_main_file, workspace_name},
+    os_helper::{run_command_with_limits, run_limited_command},
+    test_handler::run_test_case,
+    parser::escape,
+};
+
+
+pub fn run_coverage(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[COVERAGE] Error reading repos directory!");
+        std::process::exit(1);
+    }
+
+    for submission in submissions.iter_mut() {
+        if !submission.cloned {
+            continue;
+        }
+
+        for task in tasks_to_check(submission).iter() {
+            let task_main_file = match get_submission_main_file(submission, task) {
+                Some(main) => main,
+                None => continue,
+            };
+            let task_config = match get_task_config(config, submission, task) {
+                Some(c) => c,
+                None => continue,
+            };
+            // gcov needs a gcc build
+            let language = get_task_language(config, submission, task);
+            if !language.is_native() {
+                continue;
+            }
+
+            // gcc names the coverage files of "bin/NAME" "bin/NAME-<source>.gcno/.gcda"
+            let name = workspace_name(&submission.student_folder, task, "coverage");
+            remove_coverage_data(&name);
+            let task_command = match build_task(
+                language.as_ref(),
+                &submission.student_folder,
+                task,
+                &task_main_file,
+                "--coverage -O0",
+                "coverage",
+                &config.compile_limits
+            ) {
+                Ok(c) => c,
+                Err(e) => {
+                    println!("[COVERAGE] Error building task ({}) of ({}): {:#?}", task, submission.student_folder, e);
+                    if e.kind() == ErrorKind::TimedOut {
+                        submission.timeouts.push(format!("coverage build {}", task));
+                    }
+                    continue;
+                },
+            };
+
+            // every run adds its counts to the .gcda files
+            let tests = get_task_tests(task_config);
+            if tests.is_empty() {
+                for input in get_task_inputs(task_config).iter() {
+                    let mut command = task_command.clone();
+                    if let Some(input) = input {
+                        command = format!("{} < {}", command, escape(input));
+                    }
+                    if let Err(e) = run_limited_command(&command, &config.run_limits) {
+                        println!("[COVERAGE] Error running task ({}) of ({}): {:#?}", task, submission.student_folder, e);
+                    }
+                }
+            } else {
+                for test in tests.iter() {
+                    let work_dir = format!("./rp_workspace/run/{}_{}", name, test.name);
+                    if let Err(e) = run_test_case(&task_command, test, None, &work_dir, &config.run_limits) {
+                        println!("[COVERAGE] Error running test ({}) of task ({}) of ({}): {:#?}", test.name, task, submission.student_folder, e);
+                    }
+                }
+            }
+
+            let data_files = coverage_files(&name, ".gcda");
+            if data_files.is_empty() {
+                println!("[COVERAGE] No coverage data for task ({}) of ({})", task, submission.student_folder);
+                continue;
+            }
+            let summary = match run_command_with_limits(&format!("gcov -b -f -m -n {}", data_files.join(" ")), &config.compile_limits) {
+                Ok(s) => s,
+                Err(e) => {
+                    println!("[COVERAGE] Error running gcov on task ({}) of ({}): {:#?}", task, submission.student_folder, e);
+                    if e.kind() == ErrorKind::TimedOut {
+                        submission.timeouts.push(format!("gcov {}", task));
+                    }
+                    continue;
+                },
+            };
+            save_coverage_to_submission(submission, task, parse_gcov_summary(&summary));
+        }
+    }
+}
+
+// paths of the coverage files of a build in rp_workspace/bin
+fn coverage_files(name: &str, extension: &str) -> Vec<String> {
+    let entries = match fs::read_dir("./rp_workspace/bin") {
+        Ok(e) => e,
+        Err(_) => return vec![],
+    };
+    let mut files: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|file| file.starts_with(&format!("{}-", name)) && file.ends_with(extension))
+        .map(|file| format!("./rp_workspace/bin/{}", file))
+        .collect();
+    files.sort();
+    files
+}
+
+fn remove_coverage_data(name: &str) {
+    for file in coverage_files(name, ".gcda").iter().chain(coverage_files(name, ".gcno").iter()) {
+        let _ = fs::remove_file(file);
+    }
+}
+
+// reads the summary of "gcov -b -f -n": the functions of a file are listed
+// before its "File '...'" block
+fn parse_gcov_summary(summary: &str) -> CoverageReport {
+    let function = Regex::new(r"^Function '(.+)'$").unwrap();
+    let file = Regex::new(r"^File '(.+)'$").unwrap();
+    let lines = Regex::new(r"^Lines executed:([\d.]+)% of (\d+)").unwrap();
+    let taken = Regex::new(r"^Taken at least once:([\d.]+)% of (\d+)").unwrap();
+
+    let mut report = CoverageReport::default();
+    // (name, covered lines) of the functions of the next file
+    let mut functions: Vec<(String, usize)> = vec![];
+    let mut current_function: Option<String> = None;
+    let mut in_student_file = false;
+    // gcov ends with the lines of all files together, only the first lines
+    // entry after "File" belongs to the file
+    let mut file_lines_pending = false;
+
+    for line in summary.lines() {
+        let line = line.trim();
+        if let Some(capture) = function.captures(line) {
+            current_function = Some(capture[1].to_string());
+            in_student_file = false;
+            file_lines_pending = false;
+            continue;
+        }
+        if let Some(capture) = file.captures(line) {
+            current_function = None;
+            // system headers and the test driver are not the student's code
+            in_student_file = capture[1].contains("rp_workspace/repos/");
+            file_lines_pending = in_student_file;
+            let file_functions = std::mem::take(&mut functions);
+            if in_student_file {
+                for (name, covered) in file_functions.into_iter() {
+                    if covered == 0 && !report.uncovered_functions.contains(&name) {
+                        report.uncovered_functions.push(name);
+                    }
+                }
+            }
+            continue;
+        }
+
+        if let Some(capture) = lines.captures(line) {
+            let total: usize = capture[2].parse().unwrap_or(0);
+            let covered = covered_count(&capture[1], total);
+            if let Some(name) = current_function.take() {
+                functions.push((name, covered));
+            } else if file_lines_pending {
+                report.lines_covered += covered;
+                report.lines_total += total;
+                file_lines_pending = false;
+            }
+            continue;
+        }
+
+        if let Some(capture) = taken.captures(line) {
+            if in_student_file {
+                let total: usize = capture[2].parse().unwrap_or(0);
+                report.branches_taken += covered_count(&capture[1], total);
+                report.branches_total += total;
+            }
+        }
+    }
+    report
+}
+
+// gcov only prints percentages, "50.00" of 4 => 2
+fn covered_count(percent: &str, total: usize) -> usize {
+    let percent: f64 = percent.parse().unwrap_or(0.0);
+    (percent * total as f64 / 100.0).round() as usize
+}
+
+fn save_coverage_to_submission(submission: &mut StudentProjectSubmission, task: &str, report: CoverageReport) {
+    if let Some(task1) = submission.has_task1.clone() {
+        if task1 == task {
+            submission.coverage_task1 = Some(report);
+            return;
+        }
+    }
+
+    if let Some(task2) = submission.has_task2.clone() {
+        if task2 == task {
+            submission.coverage_task2 = Some(report);
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use chrono::{DateTime, FixedOffset};
+
+use std::{collections::HashMap, path::Path};
+
+use crate::models::{student_project::StudentProjectSubmission, config::{AssignmentConfig, TaskConfig}, deadline_report::DeadlineReport, head_result::HeadResult};
+
+use super::{
+    validator::{check_dir_exists, find_accepted_folder, tasks_to_check},
+    config_handler::{get_task_config, get_task_language, get_task_tests},
+    git_commit_handler::{save_commits_to_submission, ACCEPTED_FOLDER_NAMES_TASK1, ACCEPTED_FOLDER_NAMES_TASK2},
+    git_compilation_handler::build_task,
+    test_handler::run_cases,
+    languages::get_language,
+    os_helper::{run_command, run_command_with_limits},
+    parser::escape,
+};
+
+
+// checks out the last commit before the deadline, so every later stage judges
+// the repo as it was at the deadline. The HEAD it was cloned at is kept for
+// check_deadlines and compare_with_head
+pub fn checkout_deadline_snapshots(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[DEADLINE] Error reading repos directory!");
+        std::process::exit(1);
+    }
+    // there is one working tree per repo, so with different task deadlines it
+    // is checked out at the later one. check_deadlines cuts the commits of each
+    // task at its own deadline and checkout_task_snapshots rolls back the
+    // folder of the earlier task before its final state is judged
+    let deadline = match [config.task1.deadline(), config.task2.deadline()].into_iter().flatten().max() {
+        Some(d) => d,
+        None => return,
+    };
+
+    for submission in submissions.iter_mut() {
+        if !submission.cloned {
+            continue;
+        }
+        let repo = escape(&submission.student_folder);
+
+        let head = match run_command(&format!("git -C ./rp_workspace/repos/{} rev-parse --short HEAD", repo)) {
+            Ok(h) => h.trim().to_string(),
+            Err(e) => {
+                println!("[DEADLINE] Error reading HEAD of repo ({}): {:#?}", submission.student_folder, e);
+                continue;
+            },
+        };
+        // jordan's snapshot is the last commit touching the folder
+        let snapshot = match run_command(&format!(
+            "git -C ./rp_workspace/repos/{} --no-pager log -1 --format=%h --before=\"{}\"{}",
+            repo,
+            deadline.to_rfc3339(),
+            if submission.jordan { " -- ." } else { "" }
+        )) {
+            Ok(s) => s.trim().to_string(),
+            Err(e) => {
+                println!("[DEADLINE] Error finding the deadline snapshot of repo ({}): {:#?}", submission.student_folder, e);
+                continue;
+            },
+        };
+
+        // nothing was committed in time, so there is no task to judge
+        if snapshot.is_empty() {
+            println!("[DEADLINE] Repo ({}) has no commit before the deadline: Skipping its tasks!", submission.student_folder);
+            submission.head_commit = Some(head);
+            submission.no_commit_before_deadline = true;
+            continue;
+        }
+        // jordan's submissions share one repo, so it stays on its HEAD (detached,
+        // the judge commits on top of it) and checkout_task_snapshots rolls back
+        // each folder
+        let checkout = match submission.jordan {
+            true => "checkout -q --detach".to_string(),
+            false => format!("checkout {}", snapshot),
+        };
+        // the repo stays on its HEAD and is exported like one not judged at the deadline
+        if let Err(e) = run_command(&format!("git -C ./rp_workspace/repos/{} {}", repo, checkout)) {
+            println!("[DEADLINE] Error checking out the deadline snapshot of repo ({}): {:#?}", submission.student_folder, e);
+            continue;
+        }
+        submission.head_commit = Some(head);
+        submission.snapshot_commit = Some(snapshot);
+    }
+}
+
+// puts the folder of a task with an earlier deadline back to its state at that
+// deadline, in jordan's shared repo every task folder. The result is committed
+// on top of the snapshot, so the stages that check out every commit of a task
+// and then "checkout -" come back to it. Runs after the stages reading the
+// history, which never see this commit
+pub fn checkout_task_snapshots(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[DEADLINE] Error reading repos directory!");
+        std::process::exit(1);
+    }
+    let latest = match [config.task1.deadline(), config.task2.deadline()].into_iter().flatten().max() {
+        Some(d) => d,
+        None => return,
+    };
+
+    for submission in submissions.iter_mut() {
+        if !submission.cloned || submission.snapshot_commit.is_none() {
+            continue;
+        }
+        let repo = escape(&submission.student_folder);
+
+        let mut rolled_back = vec![];
+        for task in tasks_to_check(submission).iter() {
+            let deadline = match get_task_config(config, submission, task).and_then(|c| c.deadline()) {
+                Some(d) if d < latest => d,
+                _ if submission.jordan => latest,
+                _ => continue,
+            };
+            let snapshot = match run_command(&format!(
+                "git -C ./rp_workspace/repos/{} --no-pager log -1 --format=%h --before=\"{}\"",
+                repo,
+                deadline.to_rfc3339()
+            )) {
+                Ok(s) => s.trim().to_string(),
+                Err(e) => {
+                    println!("[DEADLINE] Error finding the deadline snapshot of task ({}) of ({}): {:#?}", task, submission.student_folder, e);
+                    continue;
+                },
+            };
+
+            // a folder that did not exist at the deadline stays removed. git rm
+            // would also remove jordan's student folder once it is empty
+            let mut command = format!(
+                "git -C ./rp_workspace/repos/{0} rm -r -q --cached --ignore-unmatch -- {1} && rm -rf ./rp_workspace/repos/{0}/{1}",
+                repo, escape(task)
+            );
+            if !snapshot.is_empty() {
+                command.push_str(&format!(
+                    " && (git -C ./rp_workspace/repos/{0} checkout {1} -- {2} 2>/dev/null || true)",
+                    repo, snapshot, escape(task)
+                ));
+            }
+            match run_command(&command) {
+                Ok(_) => {
+                    refresh_task(submission, task, config);
+                    rolled_back.push(task.clone());
+                },
+                Err(e) => println!("[DEADLINE] Error rolling back task ({}) of ({}): {:#?}", task, submission.student_folder, e),
+            }
+        }
+        if rolled_back.is_empty() {
+            continue;
+        }
+
+        if let Err(e) = run_command(&format!(
+            "git -C ./rp_workspace/repos/{} -c user.name=rp_judge -c user.email=rp_judge@localhost commit -q --no-verify --allow-empty -m \"rp_judge: {} at the deadline\"",
+            repo,
+            rolled_back.join(", ")
+        )) {
+            println!("[DEADLINE] Error committing the task snapshots of repo ({}): {:#?}", submission.student_folder, e);
+        }
+    }
+}
+
+// a rolled back task folder may be gone or have another main file
+fn refresh_task(submission: &mut StudentProjectSubmission, task: &str, config: &AssignmentConfig) {
+    let folder = format!("./rp_workspace/repos/{}/{}", submission.student_folder, task);
+    let exists = Path::new(&folder).is_dir();
+    let main = match exists {
+        true => get_task_language(config, submission, task).find_main_file(&folder),
+        false => None,
+    };
+
+    if submission.has_task1.as_deref() == Some(task) {
+        submission.task1_main = main;
+        if !exists {
+            submission.has_task1 = None;
+            submission.commits_task1 = None;
+        }
+    } else if submission.has_task2.as_deref() == Some(task) {
+        submission.task2_main = main;
+        if !exists {
+            submission.has_task2 = None;
+            submission.commits_task2 = None;
+        }
+    }
+}
+
+// checks out the HEAD again and records the structure, final compilation and
+// passed test cases of each task there
+pub fn compare_with_head(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[DEADLINE] Error reading repos directory!");
+        std::process::exit(1);
+    }
+    // built when loading the config
+    let checkers = &config.instructor_programs;
+
+    for submission in submissions.iter_mut() {
+        let head = match &submission.head_commit {
+            Some(h) => h.clone(),
+            None => continue,
+        };
+        if let Err(e) = run_command(&format!(
+            "git -C ./rp_workspace/repos/{} checkout {}",
+            escape(&submission.student_folder),
+            head
+        )) {
+            println!("[DEADLINE] Error checking out HEAD of repo ({}): {:#?}", submission.student_folder, e);
+            continue;
+        }
+
+        submission.head_task1 = Some(head_result(&submission.student_folder, &config.task1, &ACCEPTED_FOLDER_NAMES_TASK1, checkers, config));
+        submission.head_task2 = Some(head_result(&submission.student_folder, &config.task2, &ACCEPTED_FOLDER_NAMES_TASK2, checkers, config));
+    }
+}
+
+fn head_result(
+    student_folder: &str,
+    task_config: &TaskConfig,
+    accepted_names: &[&str],
+    checkers: &HashMap<String, String>,
+    config: &AssignmentConfig,
+) -> HeadResult {
+    let folder = match find_accepted_folder(&format!("./rp_workspace/repos/{}", student_folder), accepted_names) {
+        Some(f) => f,
+        None => return HeadResult { folder: None, final_commit_compile: false, tests_passed: None },
+    };
+    // languages are validated when loading the config
+    let language = get_language(&task_config.language).unwrap();
+    let main_file = match language.find_main_file(&format!("./rp_workspace/repos/{}/{}", student_folder, folder)) {
+        Some(m) => m,
+        None => return HeadResult { folder: Some(folder), final_commit_compile: false, tests_passed: None },
+    };
+
+    // compiles like in compile_commits: without any output
+    let task_path = format!("./rp_workspace/repos/{}/{}", escape(student_folder), escape(&folder));
+    let final_commit_compile = language
+        .check_commands(&task_path, &main_file)
+        .iter()
+        .any(|command| matches!(run_command_with_limits(command, &language.limits(&config.compile_limits)), Ok(output) if output.is_empty()));
+
+    let tests = get_task_tests(task_config);
+    let tests_passed = if tests.is_empty() {
+        None
+    } else {
+        match build_task(language.as_ref(), student_folder, &folder, &main_file, "", "head", &language.limits(&config.compile_limits)) {
+            Ok(run_command) => Some(
+                run_cases(&run_command, &tests, checkers, student_folder, &folder, &language.limits(&config.run_limits))
+                    .iter()
+                    .filter(|r| r.passed)
+                    .count()
+            ),
+            Err(_) => Some(0),
+        }
+    };
+
+    HeadResult {
+        folder: Some(folder),
+        final_commit_compile,
+        tests_passed,
+    }
+}
+
+
+pub fn check_deadlines(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[DEADLINE] Error reading repos directory!");
+        std::process::exit(1);
+    }
+
+    for submission in submissions.iter_mut() {
+        if !submission.cloned {
+            continue;
+        }
+
+        for task in tasks_to_check(submission).iter() {
+            let deadline = match get_task_config(config, submission, task).and_then(|c| c.deadline()) {
+                Some(d) => d,
+                None => continue,
+            };
+
+            // with a deadline snapshot checked out the late commits are only reachable from the old HEAD
+            let revision = submission.head_commit.clone().unwrap_or("HEAD".to_string());
+            let commits = match task_commit_dates(&submission.student_folder, &revision, task) {
+                Ok(c) => c,
+                Err(e) => {
+                    println!("[DEADLINE] Error reading commit dates of task ({}) of ({}): {:#?}", task, submission.student_folder, e);
+                    continue;
+                },
+            };
+
+            let (on_time, late): (Vec<_>, Vec<_>) = commits.into_iter().partition(|(_, date)| *date <= deadline);
+            // git log lists the newest commit first
+            let report = DeadlineReport {
+                last_commit_before: on_time.first().map(|(commit, _)| commit.clone()),
+                commits_after: late.len(),
+                late_days: late.iter().map(|(_, date)| late_days(*date, deadline)).max().unwrap_or(0),
+            };
+
+            // later stages then only see the commits made in time
+            if config.judge_at_deadline {
+                let commits = on_time.into_iter().map(|(commit, _)| commit).collect();
+                save_commits_to_submission(submission, task, Some(commits));
+            }
+            save_deadline_to_submission(submission, task, report);
+        }
+    }
+}
+
+// (abbreviated hash, committer date) of the commits touching the task, newest first
+fn task_commit_dates(student_folder: &str, revision: &str, task: &str) -> Result<Vec<(String, DateTime<FixedOffset>)>, std::io::Error> {
+    let output = run_command(&format!(
+        "git -C ./rp_workspace/repos/{} --no-pager log --format=\"%h %cI\" {} -- {}",
+        escape(student_folder),
+        revision,
+        escape(task)
+    ))?;
+
+    let mut commits = vec![];
+    for line in output.lines() {
+        let (commit, date) = match line.split_once(' ') {
+            Some(parts) => parts,
+            None => continue,
+        };
+        match DateTime::parse_from_rfc3339(date.trim()) {
+            Ok(date) => commits.push((commit.to_string(), date)),
+            Err(e) => println!("[DEADLINE] Error parsing commit date ({}) of commit ({}): {:#?}", date, commit, e),
+        }
+    }
+    Ok(commits)
+}
+
+// started days, a commit one minute late counts as one day
+fn late_days(date: DateTime<FixedOffset>, deadline: DateTime<FixedOffset>) -> i64 {
+    let seconds = (date - deadline).num_seconds();
+    if seconds <= 0 {
+        return 0;
+    }
+    (seconds + 86_399) / 86_400
+}
+
+fn save_deadline_to_submission(submission: &mut StudentProjectSubmission, task: &str, report: DeadlineReport) {
+    if let Some(task1) = submission.has_task1.clone() {
+        if task1 == task {
+            submission.deadline_task1 = Some(report);
+            return;
+        }
+    }
+
+    if let Some(task2) = submission.has_task2.clone() {
+        if task2 == task {
+            submission.deadline_task2 = Some(report);
+        }
+    }
+}
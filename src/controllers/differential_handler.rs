@@ -0,0 +1,276 @@
+use std::{fs, io::{Error, ErrorKind}};
+
+use crate::models::{student_project::StudentProjectSubmission, config::{AssignmentConfig, ResourceLimits, TaskConfig}, test_case::CompareMode, differential_result::{DifferentialResult, DifferentialFailure}};
+
+use super::{
+    validator::check_dir_exists,
+    config_handler::get_task_language,
+    git_compilation_handler::{build_task, get_submission_main_file, workspace_name},
+    os_helper::{run_limited_command, CommandOutput},
+    test_handler::{compare_output, first_different_line},
+    parser::escape,
+};
+
+// runs of the student program and the reference spent on shrinking one input
+const MAX_SHRINK_RUNS: usize = 200;
+
+// generated input with the output of the reference solution
+struct GeneratedCase {
+    seed: u64,
+    input: String,
+    expected: String,
+}
+
+struct DifferentialSetup {
+    reference: String,
+    validator: Option<String>,
+    cases: Vec<GeneratedCase>,
+}
+
+pub fn run_differential_tests(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[DIFFERENTIAL] Error reading repos directory!");
+        std::process::exit(1);
+    }
+
+    // the inputs and expected outputs are the same for every student
+    let setup_task1 = prepare_setup("task1", &config.task1, config);
+    let setup_task2 = prepare_setup("task2", &config.task2, config);
+
+    for submission in submissions.iter_mut() {
+        if !submission.cloned {
+            continue;
+        }
+
+        let tasks = [
+            (submission.has_task1.clone(), &config.task1, &setup_task1),
+            (submission.has_task2.clone(), &config.task2, &setup_task2),
+        ];
+        for (task, task_config, setup) in tasks.iter() {
+            let (task, setup) = match (task, setup) {
+                (Some(task), Some(setup)) => (task, setup),
+                _ => continue,
+            };
+            let task_main_file = match get_submission_main_file(submission, task) {
+                Some(main) => main,
+                None => continue,
+            };
+            let language = get_task_language(config, submission, task);
+
+            let run_command = match build_task(
+                language.as_ref(),
+                &submission.student_folder,
+                task,
+                &task_main_file,
+                "",
+                "differential",
+                &language.limits(&config.compile_limits)
+            ) {
+                Ok(c) => c,
+                Err(e) => {
+                    println!("[DIFFERENTIAL] Error building task ({}) of ({}): {:#?}", task, submission.student_folder, e);
+                    if e.kind() == ErrorKind::TimedOut {
+                        submission.timeouts.push(format!("differential build {}", task));
+                    }
+                    continue;
+                },
+            };
+
+            let input_file = format!(
+                "./rp_workspace/run/{}.in",
+                workspace_name(&submission.student_folder, task, "differential")
+            );
+            let mut result = DifferentialResult { runs: 0, failed_runs: 0, failure: None };
+            let compare = task_config.compare_mode();
+            for case in setup.cases.iter() {
+                let output = match run_on_input(&run_command, &case.input, &input_file, &language.limits(&config.run_limits)) {
+                    Ok(o) => o,
+                    Err(e) => {
+                        println!("[DIFFERENTIAL] Error running task ({}) of ({}): {:#?}", task, submission.student_folder, e);
+                        continue;
+                    },
+                };
+                result.runs += 1;
+                if output.timed_out {
+                    submission.timeouts.push(format!("differential {} seed {}", task, case.seed));
+                }
+                let message = match failure_message(&case.expected, &output, &compare) {
+                    Some(m) => m,
+                    None => continue,
+                };
+                result.failed_runs += 1;
+                if result.failure.is_some() {
+                    continue;
+                }
+
+                let failure = DifferentialFailure {
+                    seed: case.seed,
+                    input: case.input.clone(),
+                    expected: case.expected.clone(),
+                    actual: output.stdout,
+                    message,
+                    shrunk: false,
+                };
+                result.failure = Some(match task_config.shrink {
+                    true => shrink_failure(failure, &run_command, setup, &compare, &input_file, &language.limits(&config.run_limits)),
+                    false => failure,
+                });
+            }
+            save_differential_result_to_submission(submission, task, result);
+        }
+    }
+}
+
+// generates the cases of a task with the instructor programs
+fn prepare_setup(name: &str, task_config: &TaskConfig, config: &AssignmentConfig) -> Option<DifferentialSetup> {
+    // built when loading the config
+    let program = |path: &Option<String>| path.as_ref().and_then(|p| config.instructor_programs.get(p)).cloned();
+    let (generator, reference) = match (program(&task_config.generator), program(&task_config.reference)) {
+        (Some(g), Some(r)) => (g, r),
+        _ => return None,
+    };
+    let validator = program(&task_config.validator);
+
+    let input_file = format!("./rp_workspace/run/{}.in", workspace_name("reference", name, "differential"));
+    let mut cases = vec![];
+    for i in 0..task_config.random_tests {
+        let seed = task_config.random_seed + i as u64;
+        let generated = match run_limited_command(&format!("{} {}", generator, seed), &config.run_limits) {
+            Ok(o) if !o.timed_out && o.status == Some(0) => o.stdout,
+            Ok(o) => {
+                println!("[DIFFERENTIAL] Generator of ({}) failed for seed {}: {}", name, seed, o.stderr);
+                continue;
+            },
+            Err(e) => {
+                println!("[DIFFERENTIAL] Error running generator of ({}) for seed {}: {:#?}", name, seed, e);
+                continue;
+            },
+        };
+        match run_reference(&reference, &generated, &input_file, &config.run_limits) {
+            Some(expected) => cases.push(GeneratedCase { seed, input: generated, expected }),
+            None => println!("[DIFFERENTIAL] Reference solution of ({}) failed for seed {}", name, seed),
+        };
+    }
+    Some(DifferentialSetup { reference, validator, cases })
+}
+
+// removes chunks of lines from the input as long as the validator accepts
+// it, the reference solution finishes on it and the student program still
+// fails. Without a validator the input is kept as generated
+fn shrink_failure(
+    failure: DifferentialFailure,
+    run_command: &str,
+    setup: &DifferentialSetup,
+    compare: &CompareMode,
+    input_file: &str,
+    limits: &ResourceLimits,
+) -> DifferentialFailure {
+    let validator = match &setup.validator {
+        Some(v) => v,
+        None => return failure,
+    };
+    let mut best = failure;
+    let mut lines: Vec<String> = best.input.lines().map(|l| l.to_string()).collect();
+    let mut chunk = lines.len() / 2;
+    let mut runs = 0;
+
+    while chunk > 0 && runs < MAX_SHRINK_RUNS {
+        let mut start = 0;
+        let mut removed = false;
+        while start < lines.len() && runs < MAX_SHRINK_RUNS {
+            let mut candidate = lines.clone();
+            candidate.drain(start..(start + chunk).min(lines.len()));
+            let input = candidate.iter().map(|l| format!("{}\n", l)).collect::<String>();
+            runs += 1;
+
+            // dropping lines easily breaks the input format, e.g. a count
+            // in the first line
+            if !is_valid_input(validator, &input, input_file, limits) {
+                start += chunk;
+                continue;
+            }
+            let expected = match run_reference(&setup.reference, &input, input_file, limits) {
+                Some(e) => e,
+                None => {
+                    start += chunk;
+                    continue;
+                },
+            };
+            let output = match run_on_input(run_command, &input, input_file, limits) {
+                Ok(o) => o,
+                Err(_) => {
+                    start += chunk;
+                    continue;
+                },
+            };
+            match failure_message(&expected, &output, compare) {
+                Some(message) => {
+                    best = DifferentialFailure {
+                        seed: best.seed,
+                        input,
+                        expected,
+                        actual: output.stdout,
+                        message,
+                        shrunk: true,
+                    };
+                    lines = candidate;
+                    removed = true;
+                },
+                None => start += chunk,
+            }
+        }
+        if !removed {
+            chunk /= 2;
+        }
+    }
+    best
+}
+
+fn is_valid_input(validator: &str, input: &str, input_file: &str, limits: &ResourceLimits) -> bool {
+    matches!(run_on_input(validator, input, input_file, limits), Ok(o) if !o.timed_out && o.status == Some(0))
+}
+
+// output of the reference solution, None if it did not finish successfully
+fn run_reference(reference: &str, input: &str, input_file: &str, limits: &ResourceLimits) -> Option<String> {
+    match run_on_input(reference, input, input_file, limits) {
+        Ok(o) if !o.timed_out && o.status == Some(0) => Some(o.stdout),
+        _ => None,
+    }
+}
+
+fn run_on_input(command: &str, input: &str, input_file: &str, limits: &ResourceLimits) -> Result<CommandOutput, Error> {
+    fs::write(input_file, input)?;
+    run_limited_command(&format!("{} < {}", command, escape(input_file)), limits)
+}
+
+// reason the run differs from the reference, None if it matches
+fn failure_message(expected: &str, output: &CommandOutput, compare: &CompareMode) -> Option<String> {
+    if output.timed_out {
+        return Some("timed out".to_string());
+    }
+    if output.status.is_none() {
+        return Some("killed by a signal".to_string());
+    }
+    if compare_output(expected, &output.stdout, compare) {
+        return None;
+    }
+    Some(match first_different_line(expected, &output.stdout) {
+        Some(line) => format!("stdout differs from line {}", line),
+        None => "stdout differs".to_string(),
+    })
+}
+
+fn save_differential_result_to_submission(submission: &mut StudentProjectSubmission, task: &str, result: DifferentialResult) {
+    if let Some(task1) = submission.has_task1.clone() {
+        if task1 == task {
+            submission.differential_task1 = Some(result);
+            return;
+        }
+    }
+
+    if let Some(task2) = submission.has_task2.clone() {
+        if task2 == task {
+            submission.differential_task2 = Some(result);
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use std::error::Error;
-use chrono::Datelike;
-use xlsxwriter::{Workbook, FormatUnderline, FormatColor};
+use xlsxwriter::{Workbook, FormatUnderline, FormatColor, Worksheet, Format, ChartType};
 
-use crate::models::student_project::StudentProjectSubmission;
+use crate::models::{student_project::StudentProjectSubmission, code_metrics::CodeMetrics, shared_history::SharedHistory};
+
+use super::timeline_handler::sparkline;
 
 pub fn export_to_xlsx(submissions: Vec<StudentProjectSubmission>, file_path: &str) -> Result<(), Box<dyn Error>> {
     let workbook = Workbook::new(file_path)?;
@@ -11,12 +12,71 @@ pub fn export_to_xlsx(submissions: Vec<StudentProjectSubmission>, file_path: &st
     sheet.set_column(0, 0, 25.0, None)?;
     sheet.set_column(1, 5, 10.0, None)?;
     sheet.set_column(6, 7, 17.0, None)?;
-    sheet.set_column(8, 14, 27.0, None)?;
+    sheet.set_column(8, 16, 27.0, None)?;
+    sheet.set_column(17, 26, 30.0, None)?;
+    sheet.set_column(27, 30, 20.0, None)?;
+    sheet.set_column(31, 32, 60.0, None)?;
+    sheet.set_column(33, 33, 40.0, None)?;
+    sheet.set_column(34, 37, 25.0, None)?;
+    sheet.set_column(38, 41, 22.0, None)?;
+    sheet.set_column(42, 43, 40.0, None)?;
+    sheet.set_column(44, 49, 20.0, None)?;
+    sheet.set_column(50, 55, 22.0, None)?;
+    sheet.set_column(56, 59, 22.0, None)?;
+    sheet.set_column(60, 61, 40.0, None)?;
+    sheet.set_column(62, 69, 22.0, None)?;
+    sheet.set_column(70, 73, 22.0, None)?;
+    sheet.set_column(74, 75, 40.0, None)?;
+    sheet.set_column(76, 89, 20.0, None)?;
+    sheet.set_column(90, 91, 30.0, None)?;
+    sheet.set_column(92, 93, 22.0, None)?;
+    sheet.set_column(94, 99, 25.0, None)?;
+    sheet.set_column(100, 105, 25.0, None)?;
+    sheet.set_column(106, 106, 22.0, None)?;
+    sheet.set_column(107, 109, 20.0, None)?;
+    sheet.set_column(110, 110, 60.0, None)?;
+    sheet.set_column(111, 113, 30.0, None)?;
+    sheet.set_column(114, 114, 22.0, None)?;
+    sheet.set_column(115, 115, 40.0, None)?;
+    sheet.set_column(116, 116, 18.0, None)?;
+    sheet.set_column(117, 118, 30.0, None)?;
+    sheet.set_column(119, 119, 25.0, None)?;
 
     // Write the header row to the sheet
-    let headers = ["student_folder", "git_repo", "cloned", "has_task1", "has_task2", "total_commits", "last_commit", "gcc_standard", "commits_task1", "commits_task2", 
-    "all_commits_compile_task1", "all_commits_compile_task2", "final_commit_compile_task1", 
-    "final_commit_compile_task2", "successful_compiles_task1", "successful_compiles_task2"];
+    let headers = ["student_folder", "git_repo", "cloned", "has_task1", "has_task2", "total_commits", "last_commit", "commits_task1", "commits_task2", 
+    "commit_quality_task1", "commit_quality_task2", "all_commits_compile_task1", "all_commits_compile_task2", "final_commit_compile_task1", 
+    "final_commit_compile_task2", "successful_compiles_task1", "successful_compiles_task2",
+    "valgrind_definitely_lost_task1", "valgrind_definitely_lost_task2", "valgrind_indirectly_lost_task1", 
+    "valgrind_indirectly_lost_task2", "valgrind_possibly_lost_task1", "valgrind_possibly_lost_task2",
+    "valgrind_invalid_reads_task1", "valgrind_invalid_reads_task2", "valgrind_invalid_writes_task1", 
+    "valgrind_invalid_writes_task2",
+    "min_standard_task1", "min_standard_task2", "clean_standards_task1", "clean_standards_task2",
+    "standard_constructs_task1", "standard_constructs_task2", "timeouts",
+    "sanitizer_findings_task1", "sanitizer_findings_task2", "rule_violations_task1", "rule_violations_task2",
+    "unit_tests_passed_task1", "unit_tests_passed_task2", "unit_tests_total_task1", "unit_tests_total_task2",
+    "unit_tests_failed_task1", "unit_tests_failed_task2", "tests_passed_task1", "tests_passed_task2",
+    "tests_total_task1", "tests_total_task2", "test_score_task1", "test_score_task2",
+    "random_runs_task1", "random_runs_task2", "random_failed_task1", "random_failed_task2",
+    "random_failing_seed_task1", "random_failing_seed_task2", "history_first_all_pass_task1",
+    "history_first_all_pass_task2", "history_regressions_task1", "history_regressions_task2",
+    "history_pass_trend_task1", "history_pass_trend_task2", "benchmark_cpu_secs_task1", "benchmark_cpu_secs_task2",
+    "benchmark_peak_rss_kb_task1", "benchmark_peak_rss_kb_task2", "benchmark_time_ratio_task1",
+    "benchmark_time_ratio_task2", "benchmark_memory_ratio_task1", "benchmark_memory_ratio_task2",
+    "coverage_lines_task1", "coverage_lines_task2", "coverage_branches_task1", "coverage_branches_task2",
+    "uncovered_functions_task1", "uncovered_functions_task2", "metrics_loc_task1", "metrics_loc_task2",
+    "metrics_logical_loc_task1", "metrics_logical_loc_task2", "metrics_comment_ratio_task1",
+    "metrics_comment_ratio_task2", "metrics_functions_task1", "metrics_functions_task2",
+    "metrics_max_complexity_task1", "metrics_max_complexity_task2", "metrics_avg_complexity_task1",
+    "metrics_avg_complexity_task2", "metrics_max_nesting_task1", "metrics_max_nesting_task2",
+    "metrics_longest_function_task1", "metrics_longest_function_task2", "style_deviations_task1",
+    "style_deviations_task2", "deadline_last_commit_task1", "deadline_last_commit_task2",
+    "commits_after_deadline_task1", "commits_after_deadline_task2", "late_days_task1", "late_days_task2",
+    "snapshot_commit", "head_commit", "head_final_commit_compile_task1", "head_final_commit_compile_task2",
+    "head_tests_passed_task1", "head_tests_passed_task2", "timestamp_anomalies",
+    "authors", "main_author_share", "foreign_commits", "activity",
+    "judged_ref", "branches", "tags", "shared_history_group", "same_repo_as",
+    "hygiene_findings", "benchmark_failure_task1", "benchmark_failure_task2",
+    "no_commit_before_deadline"];
     let header_format = workbook
         .add_format()
         .set_bold();
@@ -85,12 +145,12 @@ pub fn export_to_xlsx(submissions: Vec<StudentProjectSubmission>, file_path: &st
             }
 
             if *header == "last_commit" {
-                let fmt = if let Some(date) = submission.last_commit_date {
-                    if date.day() > 6 && date.month() == 1 {
-                        Some(&red_format)
-                    } else {
-                        Some(&header_format)
-                    }
+                // red if any task has commits after its deadline
+                let is_late = [&submission.deadline_task1, &submission.deadline_task2]
+                    .iter()
+                    .any(|report| report.as_ref().map(|r| r.commits_after > 0).unwrap_or(false));
+                let fmt = if is_late {
+                    Some(&red_format)
                 } else {
                     Some(&header_format)
                 };
@@ -104,15 +164,6 @@ pub fn export_to_xlsx(submissions: Vec<StudentProjectSubmission>, file_path: &st
                 }
             }
 
-            if *header == "gcc_standard" {
-                sheet.write_string(
-                    row.try_into().unwrap(), 
-                    column,
-                    &submission.gcc_standard.clone().unwrap_or_else(|| "".to_string()), 
-                    Some(&header_format)
-                )?;
-            }
-
             if *header == "total_commits" {
                 if let Some(val) = submission.total_commits {
                     sheet.write_number(
@@ -156,6 +207,27 @@ pub fn export_to_xlsx(submissions: Vec<StudentProjectSubmission>, file_path: &st
                 }
             }
 
+            if *header == "commit_quality_task1" || *header == "commit_quality_task2" {
+                let quality = if *header == "commit_quality_task1" {
+                    &submission.commit_quality_task1
+                } else {
+                    &submission.commit_quality_task2
+                };
+                if let Some(quality) = quality {
+                    let format = if quality.score >= 50.0 {
+                        Some(&green_format)
+                    } else {
+                        Some(&red_format)
+                    };
+                    sheet.write_number(
+                        row.try_into().unwrap(), 
+                        column,
+                        quality.score, 
+                        format
+                    )?;
+                }
+            }
+
             if *header == "has_task1" {
                 if let Some(val) = submission.has_task1.clone() {
                     sheet.write_string(
@@ -288,11 +360,1280 @@ pub fn export_to_xlsx(submissions: Vec<StudentProjectSubmission>, file_path: &st
                 }
             }
 
-            col += 1;
-        }
+            if header.starts_with("valgrind_") {
+                let report = if header.ends_with("_task1") {
+                    &submission.valgrind_task1
+                } else {
+                    &submission.valgrind_task2
+                };
+                if let Some(report) = report {
+                    let value = match header.trim_end_matches("_task1").trim_end_matches("_task2") {
+                        "valgrind_definitely_lost" => report.definitely_lost as f64,
+                        "valgrind_indirectly_lost" => report.indirectly_lost as f64,
+                        "valgrind_possibly_lost" => report.possibly_lost as f64,
+                        "valgrind_invalid_reads" => report.invalid_reads as f64,
+                        _ => report.invalid_writes as f64,
+                    };
+                    let format = if value == 0.0 {
+                        Some(&green_format)
+                    } else {
+                        Some(&red_format)
+                    };
+                    sheet.write_number(
+                        row.try_into().unwrap(), 
+                        column,
+                        value, 
+                        format
+                    )?;
+                }
+            }
 
-        
-    }
-    workbook.close()?;
+            if *header == "min_standard_task1" || *header == "min_standard_task2" {
+                let report = if *header == "min_standard_task1" {
+                    &submission.standards_task1
+                } else {
+                    &submission.standards_task2
+                };
+                if let Some(report) = report {
+                    match &report.minimum_standard {
+                        Some(standard) => sheet.write_string(
+                            row.try_into().unwrap(), 
+                            column,
+                            standard, 
+                            Some(&header_format)
+                        )?,
+                        None => sheet.write_boolean(
+                            row.try_into().unwrap(), 
+                            column,
+                            false, 
+                            Some(&red_format)
+                        )?,
+                    };
+                }
+            }
+
+            if *header == "clean_standards_task1" || *header == "clean_standards_task2" {
+                let report = if *header == "clean_standards_task1" {
+                    &submission.standards_task1
+                } else {
+                    &submission.standards_task2
+                };
+                if let Some(report) = report {
+                    sheet.write_string(
+                        row.try_into().unwrap(), 
+                        column,
+                        &report.clean_standards.join(", "), 
+                        None
+                    )?;
+                }
+            }
+
+            if *header == "standard_constructs_task1" || *header == "standard_constructs_task2" {
+                let report = if *header == "standard_constructs_task1" {
+                    &submission.standards_task1
+                } else {
+                    &submission.standards_task2
+                };
+                if let Some(report) = report {
+                    let constructs = report.constructs
+                        .iter()
+                        .map(|c| match c.line {
+                            Some(line) => format!("[{}] line {}: {}", c.standard, line, c.message),
+                            None => format!("[{}] {}", c.standard, c.message),
+                        })
+                        .collect::<Vec<String>>()
+                        .join("; ");
+                    sheet.write_string(
+                        row.try_into().unwrap(), 
+                        column,
+                        &constructs, 
+                        None
+                    )?;
+                }
+            }
+
+            if *header == "timeouts" && !submission.timeouts.is_empty() {
+                sheet.write_string(
+                    row.try_into().unwrap(), 
+                    column,
+                    &submission.timeouts.join(", "), 
+                    Some(&red_format)
+                )?;
+            }
+
+            if *header == "sanitizer_findings_task1" || *header == "sanitizer_findings_task2" {
+                let findings = if *header == "sanitizer_findings_task1" {
+                    &submission.sanitizer_findings_task1
+                } else {
+                    &submission.sanitizer_findings_task2
+                };
+                if let Some(findings) = findings {
+                    let format = if findings.is_empty() {
+                        Some(&green_format)
+                    } else {
+                        Some(&red_format)
+                    };
+                    sheet.write_number(
+                        row.try_into().unwrap(), 
+                        column,
+                        findings.len() as f64, 
+                        format
+                    )?;
+                }
+            }
+
+            if *header == "rule_violations_task1" || *header == "rule_violations_task2" {
+                let violations = if *header == "rule_violations_task1" {
+                    &submission.rule_violations_task1
+                } else {
+                    &submission.rule_violations_task2
+                };
+                if let Some(violations) = violations {
+                    let format = if violations.is_empty() {
+                        Some(&green_format)
+                    } else {
+                        Some(&red_format)
+                    };
+                    sheet.write_number(
+                        row.try_into().unwrap(), 
+                        column,
+                        violations.len() as f64, 
+                        format
+                    )?;
+                }
+            }
+
+            if header.starts_with("unit_tests_") {
+                let results = if header.ends_with("_task1") {
+                    &submission.unit_tests_task1
+                } else {
+                    &submission.unit_tests_task2
+                };
+                if let Some(results) = results {
+                    let passed = results.iter().filter(|r| r.passed).count();
+                    // a driver that reported nothing did not pass
+                    let format = if passed == results.len() && !results.is_empty() {
+                        Some(&green_format)
+                    } else {
+                        Some(&red_format)
+                    };
+                    match header.trim_end_matches("_task1").trim_end_matches("_task2") {
+                        "unit_tests_passed" => sheet.write_number(
+                            row.try_into().unwrap(), 
+                            column,
+                            passed as f64, 
+                            format
+                        )?,
+                        "unit_tests_total" => sheet.write_number(
+                            row.try_into().unwrap(), 
+                            column,
+                            results.len() as f64, 
+                            None
+                        )?,
+                        _ => sheet.write_string(
+                            row.try_into().unwrap(), 
+                            column,
+                            &results
+                                .iter()
+                                .filter(|r| !r.passed)
+                                .map(|r| r.name.as_str())
+                                .collect::<Vec<&str>>()
+                                .join(", "), 
+                            None
+                        )?,
+                    };
+                }
+            }
+
+            if header.starts_with("tests_") || header.starts_with("test_score_") {
+                let results = if header.ends_with("_task1") {
+                    &submission.test_results_task1
+                } else {
+                    &submission.test_results_task2
+                };
+                if let Some(results) = results {
+                    let passed = results.iter().filter(|r| r.passed).count();
+                    let format = if passed == results.len() {
+                        Some(&green_format)
+                    } else {
+                        Some(&red_format)
+                    };
+                    // weighted percentage of the cases
+                    let weight: f64 = results.iter().map(|r| r.weight).sum();
+                    let score = if weight > 0.0 {
+                        100.0 * results.iter().map(|r| r.score).sum::<f64>() / weight
+                    } else {
+                        0.0
+                    };
+                    match header.trim_end_matches("_task1").trim_end_matches("_task2") {
+                        "tests_passed" => sheet.write_number(
+                            row.try_into().unwrap(), 
+                            column,
+                            passed as f64, 
+                            format
+                        )?,
+                        "tests_total" => sheet.write_number(
+                            row.try_into().unwrap(), 
+                            column,
+                            results.len() as f64, 
+                            None
+                        )?,
+                        _ => sheet.write_number(
+                            row.try_into().unwrap(), 
+                            column,
+                            score, 
+                            format
+                        )?,
+                    };
+                }
+            }
+
+            if header.starts_with("random_") {
+                let result = if header.ends_with("_task1") {
+                    &submission.differential_task1
+                } else {
+                    &submission.differential_task2
+                };
+                if let Some(result) = result {
+                    let format = if result.failed_runs == 0 {
+                        Some(&green_format)
+                    } else {
+                        Some(&red_format)
+                    };
+                    match header.trim_end_matches("_task1").trim_end_matches("_task2") {
+                        "random_runs" => sheet.write_number(
+                            row.try_into().unwrap(), 
+                            column,
+                            result.runs as f64, 
+                            None
+                        )?,
+                        "random_failed" => sheet.write_number(
+                            row.try_into().unwrap(), 
+                            column,
+                            result.failed_runs as f64, 
+                            format
+                        )?,
+                        _ => if let Some(failure) = &result.failure {
+                            sheet.write_number(
+                                row.try_into().unwrap(), 
+                                column,
+                                failure.seed as f64, 
+                                format
+                            )?
+                        },
+                    };
+                }
+            }
+
+            if header.starts_with("history_") {
+                let history = if header.ends_with("_task1") {
+                    &submission.test_history_task1
+                } else {
+                    &submission.test_history_task2
+                };
+                if let Some(history) = history {
+                    match header.trim_end_matches("_task1").trim_end_matches("_task2") {
+                        "history_first_all_pass" => match &history.first_all_passing {
+                            Some(commit) => sheet.write_string(
+                                row.try_into().unwrap(), 
+                                column,
+                                commit, 
+                                Some(&green_format)
+                            )?,
+                            None => sheet.write_boolean(
+                                row.try_into().unwrap(), 
+                                column,
+                                false, 
+                                Some(&red_format)
+                            )?,
+                        },
+                        "history_regressions" => sheet.write_number(
+                            row.try_into().unwrap(), 
+                            column,
+                            history.regressions.len() as f64, 
+                            if history.regressions.is_empty() { Some(&green_format) } else { Some(&red_format) }
+                        )?,
+                        // passed cases per commit, oldest first
+                        _ => sheet.write_string(
+                            row.try_into().unwrap(), 
+                            column,
+                            &history.runs
+                                .iter()
+                                .map(|run| format!("{}/{}", run.passed.len(), history.total))
+                                .collect::<Vec<String>>()
+                                .join(" "), 
+                            None
+                        )?,
+                    };
+                }
+            }
+
+            if header.starts_with("benchmark_failure_") {
+                let failure = if header.ends_with("_task1") {
+                    &submission.benchmark_failure_task1
+                } else {
+                    &submission.benchmark_failure_task2
+                };
+                if let Some(failure) = failure {
+                    sheet.write_string(
+                        row.try_into().unwrap(), 
+                        column,
+                        failure, 
+                        Some(&red_format)
+                    )?;
+                }
+            } else if header.starts_with("benchmark_") {
+                let result = if header.ends_with("_task1") {
+                    &submission.benchmark_task1
+                } else {
+                    &submission.benchmark_task2
+                };
+                if let Some(result) = result {
+                    let format = if result.too_slow {
+                        Some(&red_format)
+                    } else {
+                        Some(&green_format)
+                    };
+                    let (value, format) = match header.trim_end_matches("_task1").trim_end_matches("_task2") {
+                        "benchmark_cpu_secs" => (result.cpu_secs, None),
+                        "benchmark_peak_rss_kb" => (result.peak_rss_kb as f64, None),
+                        "benchmark_time_ratio" => (result.time_ratio, format),
+                        _ => (result.memory_ratio, format),
+                    };
+                    sheet.write_number(
+                        row.try_into().unwrap(), 
+                        column,
+                        value, 
+                        format
+                    )?;
+                }
+            }
+
+            if header.starts_with("coverage_") || header.starts_with("uncovered_functions_") {
+                let report = if header.ends_with("_task1") {
+                    &submission.coverage_task1
+                } else {
+                    &submission.coverage_task2
+                };
+                if let Some(report) = report {
+                    // percentages, nothing to cover counts as fully covered
+                    let (covered, total) = match header.trim_end_matches("_task1").trim_end_matches("_task2") {
+                        "coverage_lines" => (report.lines_covered, report.lines_total),
+                        "coverage_branches" => (report.branches_taken, report.branches_total),
+                        _ => (0, 0),
+                    };
+                    if header.starts_with("uncovered_functions_") {
+                        sheet.write_string(
+                            row.try_into().unwrap(), 
+                            column,
+                            &report.uncovered_functions.join(", "), 
+                            None
+                        )?;
+                    } else {
+                        let percent = if total == 0 { 100.0 } else { 100.0 * covered as f64 / total as f64 };
+                        sheet.write_number(
+                            row.try_into().unwrap(), 
+                            column,
+                            percent, 
+                            None
+                        )?;
+                    }
+                }
+            }
+
+            if header.starts_with("metrics_") {
+                let metrics = if header.ends_with("_task1") {
+                    &submission.metrics_task1
+                } else {
+                    &submission.metrics_task2
+                };
+                if let Some(metrics) = metrics {
+                    let metric = header.trim_end_matches("_task1").trim_end_matches("_task2");
+                    if metric == "metrics_longest_function" {
+                        if let Some(name) = &metrics.longest_function {
+                            sheet.write_string(
+                                row.try_into().unwrap(), 
+                                column,
+                                &format!("{} ({} lines)", name, metrics.longest_function_lines), 
+                                None
+                            )?;
+                        }
+                    } else {
+                        let value = match metric {
+                            "metrics_loc" => metrics.physical_loc as f64,
+                            "metrics_logical_loc" => metrics.logical_loc as f64,
+                            "metrics_comment_ratio" => comment_ratio(metrics),
+                            "metrics_functions" => metrics.functions as f64,
+                            "metrics_max_complexity" => metrics.max_complexity as f64,
+                            "metrics_avg_complexity" => metrics.average_complexity,
+                            _ => metrics.max_nesting as f64,
+                        };
+                        sheet.write_number(
+                            row.try_into().unwrap(), 
+                            column,
+                            value, 
+                            None
+                        )?;
+                    }
+                }
+            }
+
+            if *header == "style_deviations_task1" || *header == "style_deviations_task2" {
+                let report = if *header == "style_deviations_task1" {
+                    &submission.style_task1
+                } else {
+                    &submission.style_task2
+                };
+                if let Some(report) = report {
+                    let format = if report.deviating_lines == 0 {
+                        Some(&green_format)
+                    } else {
+                        Some(&red_format)
+                    };
+                    sheet.write_number(
+                        row.try_into().unwrap(), 
+                        column,
+                        report.deviating_lines as f64, 
+                        format
+                    )?;
+                }
+            }
+
+            if *header == "deadline_last_commit_task1" || *header == "deadline_last_commit_task2" {
+                let report = if *header == "deadline_last_commit_task1" {
+                    &submission.deadline_task1
+                } else {
+                    &submission.deadline_task2
+                };
+                if let Some(report) = report {
+                    match &report.last_commit_before {
+                        Some(commit) => sheet.write_string(
+                            row.try_into().unwrap(), 
+                            column,
+                            commit, 
+                            None
+                        )?,
+                        None => sheet.write_string(
+                            row.try_into().unwrap(), 
+                            column,
+                            "none", 
+                            Some(&red_format)
+                        )?,
+                    }
+                }
+            }
+
+            if *header == "commits_after_deadline_task1" || *header == "commits_after_deadline_task2" {
+                let report = if *header == "commits_after_deadline_task1" {
+                    &submission.deadline_task1
+                } else {
+                    &submission.deadline_task2
+                };
+                if let Some(report) = report {
+                    let format = if report.commits_after == 0 {
+                        Some(&green_format)
+                    } else {
+                        Some(&red_format)
+                    };
+                    sheet.write_number(
+                        row.try_into().unwrap(), 
+                        column,
+                        report.commits_after as f64, 
+                        format
+                    )?;
+                }
+            }
+
+            if *header == "late_days_task1" || *header == "late_days_task2" {
+                let report = if *header == "late_days_task1" {
+                    &submission.deadline_task1
+                } else {
+                    &submission.deadline_task2
+                };
+                if let Some(report) = report {
+                    let format = if report.late_days == 0 {
+                        Some(&green_format)
+                    } else {
+                        Some(&red_format)
+                    };
+                    sheet.write_number(
+                        row.try_into().unwrap(), 
+                        column,
+                        report.late_days as f64, 
+                        format
+                    )?;
+                }
+            }
+
+            if *header == "snapshot_commit" {
+                if let Some(commit) = &submission.snapshot_commit {
+                    sheet.write_string(
+                        row.try_into().unwrap(), 
+                        column,
+                        commit, 
+                        None
+                    )?;
+                } else if submission.head_commit.is_some() {
+                    // judged at the deadline, but nothing was committed in time
+                    sheet.write_string(
+                        row.try_into().unwrap(), 
+                        column,
+                        "none", 
+                        Some(&red_format)
+                    )?;
+                }
+            }
+
+            // only known when judged at the deadline
+            if *header == "no_commit_before_deadline" && submission.head_commit.is_some() {
+                let format = if submission.no_commit_before_deadline {
+                    Some(&red_format)
+                } else {
+                    Some(&green_format)
+                };
+                sheet.write_boolean(
+                    row.try_into().unwrap(), 
+                    column,
+                    submission.no_commit_before_deadline, 
+                    format
+                )?;
+            }
+
+            if *header == "head_commit" {
+                if let Some(commit) = &submission.head_commit {
+                    sheet.write_string(
+                        row.try_into().unwrap(), 
+                        column,
+                        commit, 
+                        None
+                    )?;
+                }
+            }
+
+            if *header == "head_final_commit_compile_task1" || *header == "head_final_commit_compile_task2" {
+                let result = if *header == "head_final_commit_compile_task1" {
+                    &submission.head_task1
+                } else {
+                    &submission.head_task2
+                };
+                if let Some(result) = result {
+                    if result.folder.is_some() {
+                        let format = if result.final_commit_compile {
+                            Some(&green_format)
+                        } else {
+                            Some(&red_format)
+                        };
+                        sheet.write_boolean(
+                            row.try_into().unwrap(), 
+                            column,
+                            result.final_commit_compile, 
+                            format
+                        )?;
+                    }
+                }
+            }
+
+            if *header == "head_tests_passed_task1" || *header == "head_tests_passed_task2" {
+                let result = if *header == "head_tests_passed_task1" {
+                    &submission.head_task1
+                } else {
+                    &submission.head_task2
+                };
+                if let Some(passed) = result.as_ref().and_then(|r| r.tests_passed) {
+                    sheet.write_number(
+                        row.try_into().unwrap(), 
+                        column,
+                        passed as f64, 
+                        None
+                    )?;
+                }
+            }
+
+            if *header == "timestamp_anomalies" {
+                if let Some(anomalies) = &submission.timestamp_anomalies {
+                    let format = if anomalies.is_empty() {
+                        Some(&green_format)
+                    } else {
+                        Some(&red_format)
+                    };
+                    sheet.write_number(
+                        row.try_into().unwrap(), 
+                        column,
+                        anomalies.len() as f64, 
+                        format
+                    )?;
+                }
+            }
+
+            if *header == "authors" {
+                if let Some(report) = &submission.authorship {
+                    let authors = report.identities.iter().filter(|s| s.authored > 0).count();
+                    sheet.write_number(
+                        row.try_into().unwrap(), 
+                        column,
+                        authors as f64, 
+                        None
+                    )?;
+                }
+            }
+
+            if *header == "main_author_share" {
+                if let Some(share) = submission.authorship.as_ref().and_then(|r| r.identities.first()) {
+                    sheet.write_number(
+                        row.try_into().unwrap(), 
+                        column,
+                        share.share, 
+                        None
+                    )?;
+                }
+            }
+
+            if *header == "foreign_commits" {
+                let report = submission.authorship.as_ref();
+                // only known if the student's email or username is
+                let is_known = report.map(|r| r.identities.iter().any(|s| s.matches_student.is_some())).unwrap_or(false);
+                if let (Some(report), true) = (report, is_known) {
+                    let format = if report.foreign_commits.is_empty() {
+                        Some(&green_format)
+                    } else {
+                        Some(&red_format)
+                    };
+                    sheet.write_number(
+                        row.try_into().unwrap(), 
+                        column,
+                        report.foreign_commits.len() as f64, 
+                        format
+                    )?;
+                }
+            }
+
+            if *header == "activity" {
+                if let Some(activity) = &submission.activity {
+                    sheet.write_string(
+                        row.try_into().unwrap(), 
+                        column,
+                        &sparkline(&activity.commits_per_day), 
+                        None
+                    )?;
+                }
+            }
+
+            if *header == "judged_ref" {
+                if let Some(report) = &submission.branch_report {
+                    // the configured ref is missing and the default branch was judged
+                    let format = if report.missing_ref.is_some() {
+                        Some(&red_format)
+                    } else {
+                        None
+                    };
+                    sheet.write_string(
+                        row.try_into().unwrap(), 
+                        column,
+                        &report.judged, 
+                        format
+                    )?;
+                }
+            }
+
+            if *header == "branches" {
+                if let Some(report) = &submission.branch_report {
+                    sheet.write_string(
+                        row.try_into().unwrap(), 
+                        column,
+                        &report.branches.join(", "), 
+                        None
+                    )?;
+                }
+            }
+
+            if *header == "tags" {
+                if let Some(report) = &submission.branch_report {
+                    sheet.write_string(
+                        row.try_into().unwrap(), 
+                        column,
+                        &report.tags.join(", "), 
+                        None
+                    )?;
+                }
+            }
+
+            if *header == "shared_history_group" {
+                if let Some(history) = &submission.shared_history {
+                    sheet.write_number(
+                        row.try_into().unwrap(), 
+                        column,
+                        history.group as f64, 
+                        Some(&red_format)
+                    )?;
+                }
+            }
+
+            if *header == "same_repo_as" && !submission.same_repo_as.is_empty() {
+                sheet.write_string(
+                    row.try_into().unwrap(), 
+                    column,
+                    &submission.same_repo_as.join(", "), 
+                    Some(&red_format)
+                )?;
+            }
+
+            if *header == "hygiene_findings" {
+                if let Some(findings) = &submission.hygiene {
+                    let format = if findings.is_empty() {
+                        Some(&green_format)
+                    } else {
+                        Some(&red_format)
+                    };
+                    sheet.write_number(
+                        row.try_into().unwrap(), 
+                        column,
+                        findings.len() as f64, 
+                        format
+                    )?;
+                }
+            }
+
+            col += 1;
+        }
+
+        
+    }
+
+    if submissions.iter().any(|s| s.sanitizer_findings_task1.is_some() || s.sanitizer_findings_task2.is_some()) {
+        let mut sanitizer_sheet = workbook.add_worksheet(Some("sanitizer"))?;
+        write_sanitizer_sheet(&mut sanitizer_sheet, &submissions, &header_format)?;
+    }
+
+    if submissions.iter().any(|s| s.rule_violations_task1.is_some() || s.rule_violations_task2.is_some()) {
+        let mut rules_sheet = workbook.add_worksheet(Some("rules"))?;
+        write_rules_sheet(&mut rules_sheet, &submissions, &header_format)?;
+    }
+
+    if submissions.iter().any(|s| s.test_results_task1.is_some() || s.test_results_task2.is_some()) {
+        let mut tests_sheet = workbook.add_worksheet(Some("tests"))?;
+        write_tests_sheet(&mut tests_sheet, &submissions, &header_format)?;
+    }
+
+    if submissions.iter().any(|s| s.differential_task1.is_some() || s.differential_task2.is_some()) {
+        let mut differential_sheet = workbook.add_worksheet(Some("differential"))?;
+        write_differential_sheet(&mut differential_sheet, &submissions, &header_format)?;
+    }
+
+    if submissions.iter().any(|s| s.test_history_task1.is_some() || s.test_history_task2.is_some()) {
+        let mut history_sheet = workbook.add_worksheet(Some("test_history"))?;
+        write_test_history_sheet(&mut history_sheet, &submissions, &header_format)?;
+    }
+
+    if submissions.iter().any(|s| s.metrics_history_task1.is_some() || s.metrics_history_task2.is_some()) {
+        let mut metrics_sheet = workbook.add_worksheet(Some("metrics_history"))?;
+        write_metrics_history_sheet(&mut metrics_sheet, &submissions, &header_format)?;
+    }
+
+    if submissions.iter().any(|s| s.timestamp_anomalies.as_ref().map(|a| !a.is_empty()).unwrap_or(false)) {
+        let mut timestamps_sheet = workbook.add_worksheet(Some("timestamps"))?;
+        write_timestamps_sheet(&mut timestamps_sheet, &submissions, &header_format)?;
+    }
+
+    if submissions.iter().any(|s| s.shared_history.is_some()) {
+        let mut shared_sheet = workbook.add_worksheet(Some("shared_history"))?;
+        write_shared_history_sheet(&mut shared_sheet, &submissions, &header_format)?;
+    }
+
+    if submissions.iter().any(|s| s.activity.is_some()) {
+        let mut timeline_sheet = workbook.add_worksheet(Some("timeline"))?;
+        write_timeline_sheet(&workbook, &mut timeline_sheet, &submissions, &header_format)?;
+    }
+
+    if submissions.iter().any(|s| s.commit_quality_task1.is_some() || s.commit_quality_task2.is_some()) {
+        let mut quality_sheet = workbook.add_worksheet(Some("commit_quality"))?;
+        write_commit_quality_sheet(&mut quality_sheet, &submissions, &header_format)?;
+    }
+
+    if submissions.iter().any(|s| s.authorship.is_some()) {
+        let mut authorship_sheet = workbook.add_worksheet(Some("authorship"))?;
+        write_authorship_sheet(&mut authorship_sheet, &submissions, &header_format)?;
+    }
+
+    if submissions.iter().any(|s| s.hygiene.as_ref().map(|h| !h.is_empty()).unwrap_or(false)) {
+        let mut hygiene_sheet = workbook.add_worksheet(Some("hygiene"))?;
+        write_hygiene_sheet(&mut hygiene_sheet, &submissions, &header_format)?;
+    }
+
+    workbook.close()?;
+    Ok(())
+}
+
+// one row per failing task with the input to reproduce the failure
+fn write_differential_sheet(sheet: &mut Worksheet, submissions: &[StudentProjectSubmission], header_format: &Format) -> Result<(), Box<dyn Error>> {
+    sheet.set_column(0, 0, 25.0, None)?;
+    sheet.set_column(1, 4, 10.0, None)?;
+    sheet.set_column(5, 5, 30.0, None)?;
+    sheet.set_column(6, 8, 40.0, None)?;
+
+    let headers = ["student_folder", "task", "seed", "failed_runs", "shrunk", "message", "input", "expected", "actual"];
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string(0, col.try_into().unwrap(), header, Some(header_format))?;
+    }
+
+    let mut row = 1;
+    for submission in submissions.iter() {
+        let tasks = [
+            (&submission.has_task1, &submission.differential_task1),
+            (&submission.has_task2, &submission.differential_task2),
+        ];
+        for (task, result) in tasks.iter() {
+            let (task, result, failure) = match (task, result) {
+                (Some(task), Some(result)) => match &result.failure {
+                    Some(failure) => (task, result, failure),
+                    None => continue,
+                },
+                _ => continue,
+            };
+            sheet.write_string(row, 0, &submission.student_folder, Some(header_format))?;
+            sheet.write_string(row, 1, task, None)?;
+            sheet.write_number(row, 2, failure.seed as f64, None)?;
+            sheet.write_number(row, 3, result.failed_runs as f64, None)?;
+            sheet.write_boolean(row, 4, failure.shrunk, None)?;
+            sheet.write_string(row, 5, &failure.message, None)?;
+            sheet.write_string(row, 6, &cell_text(&failure.input), None)?;
+            sheet.write_string(row, 7, &cell_text(&failure.expected), None)?;
+            sheet.write_string(row, 8, &cell_text(&failure.actual), None)?;
+            row += 1;
+        }
+    }
+    Ok(())
+}
+
+// xlsx cells hold at most 32767 characters
+fn cell_text(text: &str) -> String {
+    const MAX_CHARS: usize = 32000;
+    if text.chars().count() <= MAX_CHARS {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(MAX_CHARS).collect();
+    truncated.push_str("\n[truncated]");
+    truncated
+}
+
+// share of the lines with a comment
+fn comment_ratio(metrics: &CodeMetrics) -> f64 {
+    if metrics.physical_loc == 0 {
+        return 0.0;
+    }
+    metrics.comment_lines as f64 / metrics.physical_loc as f64
+}
+
+// one row per commit, oldest first
+fn write_metrics_history_sheet(sheet: &mut Worksheet, submissions: &[StudentProjectSubmission], header_format: &Format) -> Result<(), Box<dyn Error>> {
+    sheet.set_column(0, 0, 25.0, None)?;
+    sheet.set_column(1, 2, 10.0, None)?;
+    sheet.set_column(3, 9, 15.0, None)?;
+
+    let headers = ["student_folder", "task", "commit", "loc", "logical_loc", "comment_ratio", "functions", "max_complexity", "avg_complexity", "max_nesting"];
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string(0, col.try_into().unwrap(), header, Some(header_format))?;
+    }
+
+    let mut row = 1;
+    for submission in submissions.iter() {
+        let tasks = [
+            (&submission.has_task1, &submission.metrics_history_task1),
+            (&submission.has_task2, &submission.metrics_history_task2),
+        ];
+        for (task, history) in tasks.iter() {
+            let (task, history) = match (task, history) {
+                (Some(task), Some(history)) => (task, history),
+                _ => continue,
+            };
+            for entry in history.iter() {
+                let metrics = &entry.metrics;
+                sheet.write_string(row, 0, &submission.student_folder, Some(header_format))?;
+                sheet.write_string(row, 1, task, None)?;
+                sheet.write_string(row, 2, &entry.commit, None)?;
+                sheet.write_number(row, 3, metrics.physical_loc as f64, None)?;
+                sheet.write_number(row, 4, metrics.logical_loc as f64, None)?;
+                sheet.write_number(row, 5, comment_ratio(metrics), None)?;
+                sheet.write_number(row, 6, metrics.functions as f64, None)?;
+                sheet.write_number(row, 7, metrics.max_complexity as f64, None)?;
+                sheet.write_number(row, 8, metrics.average_complexity, None)?;
+                sheet.write_number(row, 9, metrics.max_nesting as f64, None)?;
+                row += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+// one row per commit, oldest first
+fn write_test_history_sheet(sheet: &mut Worksheet, submissions: &[StudentProjectSubmission], header_format: &Format) -> Result<(), Box<dyn Error>> {
+    sheet.set_column(0, 0, 25.0, None)?;
+    sheet.set_column(1, 5, 10.0, None)?;
+    sheet.set_column(6, 6, 40.0, None)?;
+
+    let headers = ["student_folder", "task", "commit", "built", "passed", "total", "regressed"];
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string(0, col.try_into().unwrap(), header, Some(header_format))?;
+    }
+
+    let mut row = 1;
+    for submission in submissions.iter() {
+        let tasks = [
+            (&submission.has_task1, &submission.test_history_task1),
+            (&submission.has_task2, &submission.test_history_task2),
+        ];
+        for (task, history) in tasks.iter() {
+            let (task, history) = match (task, history) {
+                (Some(task), Some(history)) => (task, history),
+                _ => continue,
+            };
+            for run in history.runs.iter() {
+                sheet.write_string(row, 0, &submission.student_folder, Some(header_format))?;
+                sheet.write_string(row, 1, task, None)?;
+                sheet.write_string(row, 2, &run.commit, None)?;
+                sheet.write_boolean(row, 3, run.built, None)?;
+                sheet.write_number(row, 4, run.passed.len() as f64, None)?;
+                sheet.write_number(row, 5, history.total as f64, None)?;
+                let regressed = history.regressions
+                    .iter()
+                    .filter(|r| r.failed_in == run.commit)
+                    .map(|r| format!("{} (passed in {})", r.test, r.passed_in))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                sheet.write_string(row, 6, &regressed, None)?;
+                row += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_tests_sheet(sheet: &mut Worksheet, submissions: &[StudentProjectSubmission], header_format: &Format) -> Result<(), Box<dyn Error>> {
+    sheet.set_column(0, 0, 25.0, None)?;
+    sheet.set_column(1, 1, 10.0, None)?;
+    sheet.set_column(2, 2, 20.0, None)?;
+    sheet.set_column(3, 6, 10.0, None)?;
+    sheet.set_column(7, 7, 60.0, None)?;
+
+    let headers = ["student_folder", "task", "test", "hidden", "passed", "score", "weight", "message"];
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string(0, col.try_into().unwrap(), header, Some(header_format))?;
+    }
+
+    let mut row = 1;
+    for submission in submissions.iter() {
+        let tasks = [
+            (&submission.has_task1, &submission.test_results_task1),
+            (&submission.has_task2, &submission.test_results_task2),
+        ];
+        for (task, results) in tasks.iter() {
+            let (task, results) = match (task, results) {
+                (Some(task), Some(results)) => (task, results),
+                _ => continue,
+            };
+            for result in results.iter() {
+                sheet.write_string(row, 0, &submission.student_folder, Some(header_format))?;
+                sheet.write_string(row, 1, task, None)?;
+                sheet.write_string(row, 2, &result.name, None)?;
+                sheet.write_boolean(row, 3, result.hidden, None)?;
+                sheet.write_boolean(row, 4, result.passed, None)?;
+                sheet.write_number(row, 5, result.score, None)?;
+                sheet.write_number(row, 6, result.weight, None)?;
+                if let Some(message) = &result.message {
+                    sheet.write_string(row, 7, message, None)?;
+                }
+                row += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+// one row per member of each group of submissions sharing commits
+fn write_shared_history_sheet(sheet: &mut Worksheet, submissions: &[StudentProjectSubmission], header_format: &Format) -> Result<(), Box<dyn Error>> {
+    sheet.set_column(0, 0, 10.0, None)?;
+    sheet.set_column(1, 1, 25.0, None)?;
+    sheet.set_column(2, 4, 15.0, None)?;
+    sheet.set_column(5, 6, 30.0, None)?;
+
+    let headers = ["group", "student_folder", "shared_commits", "shared_roots", "first_shared_commit", "first_author", "first_date"];
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string(0, col.try_into().unwrap(), header, Some(header_format))?;
+    }
+
+    let mut members: Vec<(&SharedHistory, &str)> = submissions
+        .iter()
+        .filter_map(|s| s.shared_history.as_ref().map(|h| (h, s.student_folder.as_str())))
+        .collect();
+    members.sort_by_key(|(history, _)| history.group);
+
+    for (i, (history, student_folder)) in members.iter().enumerate() {
+        let row = (i + 1).try_into().unwrap();
+        sheet.write_number(row, 0, history.group as f64, None)?;
+        sheet.write_string(row, 1, student_folder, Some(header_format))?;
+        sheet.write_number(row, 2, history.shared_commits as f64, None)?;
+        sheet.write_number(row, 3, history.shared_roots as f64, None)?;
+        sheet.write_string(row, 4, &history.first_commit, None)?;
+        sheet.write_string(row, 5, &history.first_author, None)?;
+        sheet.write_string(row, 6, &history.first_date, None)?;
+    }
+    Ok(())
+}
+
+// commits per day as a table on top and one column chart per student below it
+fn write_timeline_sheet(workbook: &Workbook, sheet: &mut Worksheet, submissions: &[StudentProjectSubmission], header_format: &Format) -> Result<(), Box<dyn Error>> {
+    let submissions: Vec<&StudentProjectSubmission> = submissions.iter().filter(|s| s.activity.is_some()).collect();
+    // every submission shares the window
+    let (start, days) = match submissions.first().and_then(|s| s.activity.as_ref()) {
+        Some(activity) => (activity.start, activity.commits_per_day.len()),
+        None => return Ok(()),
+    };
+
+    sheet.set_column(0, 0, 25.0, None)?;
+    sheet.write_string(0, 0, "student_folder", Some(header_format))?;
+    for day in 0..days {
+        let date = start + chrono::Duration::days(day as i64);
+        sheet.write_string(0, (day + 1).try_into().unwrap(), &date.format("%m-%d").to_string(), Some(header_format))?;
+    }
+    for (i, submission) in submissions.iter().enumerate() {
+        let row = (i + 1).try_into().unwrap();
+        sheet.write_string(row, 0, &submission.student_folder, Some(header_format))?;
+        if let Some(activity) = &submission.activity {
+            for (day, commits) in activity.commits_per_day.iter().enumerate() {
+                sheet.write_number(row, (day + 1).try_into().unwrap(), *commits as f64, None)?;
+            }
+        }
+    }
+
+    // a default chart is about 15 rows high
+    let last_day: u16 = days.try_into().unwrap();
+    for (i, submission) in submissions.iter().enumerate() {
+        let row: u32 = (i + 1).try_into().unwrap();
+        let mut chart = workbook.add_chart(ChartType::Column);
+        let mut series = chart.add_series(None, None)?;
+        series.set_categories("timeline", 0, 1, 0, last_day)?;
+        series.set_values("timeline", row, 1, row, last_day)?;
+        series.set_name(&submission.student_folder)?;
+        chart.add_title(&submission.student_folder)?;
+        sheet.insert_chart(submissions.len() as u32 + 2 + i as u32 * 16, 0, &chart)?;
+    }
+    Ok(())
+}
+
+fn write_commit_quality_sheet(sheet: &mut Worksheet, submissions: &[StudentProjectSubmission], header_format: &Format) -> Result<(), Box<dyn Error>> {
+    sheet.set_column(0, 0, 25.0, None)?;
+    sheet.set_column(1, 1, 10.0, None)?;
+    sheet.set_column(2, 10, 22.0, None)?;
+
+    let headers = ["student_folder", "task", "commits", "median_message_length", "short_messages", "default_messages",
+        "median_lines_changed", "largest_commit_share", "big_bang_commit", "spread_days", "score"];
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string(0, col.try_into().unwrap(), header, Some(header_format))?;
+    }
+
+    let mut row = 1;
+    for submission in submissions.iter() {
+        let tasks = [
+            (&submission.has_task1, &submission.commit_quality_task1),
+            (&submission.has_task2, &submission.commit_quality_task2),
+        ];
+        for (task, quality) in tasks.iter() {
+            let (task, quality) = match (task, quality) {
+                (Some(task), Some(quality)) => (task, quality),
+                _ => continue,
+            };
+            sheet.write_string(row, 0, &submission.student_folder, Some(header_format))?;
+            sheet.write_string(row, 1, task, None)?;
+            sheet.write_number(row, 2, quality.commits as f64, None)?;
+            sheet.write_number(row, 3, quality.median_message_length as f64, None)?;
+            sheet.write_number(row, 4, quality.short_messages as f64, None)?;
+            sheet.write_number(row, 5, quality.default_messages as f64, None)?;
+            sheet.write_number(row, 6, quality.median_lines_changed as f64, None)?;
+            sheet.write_number(row, 7, quality.largest_commit_share, None)?;
+            if let Some(commit) = &quality.big_bang_commit {
+                sheet.write_string(row, 8, commit, None)?;
+            }
+            sheet.write_number(row, 9, quality.spread_days, None)?;
+            sheet.write_number(row, 10, quality.score, None)?;
+            row += 1;
+        }
+    }
+    Ok(())
+}
+
+// one row per identity that authored, committed or co-authored a commit
+fn write_authorship_sheet(sheet: &mut Worksheet, submissions: &[StudentProjectSubmission], header_format: &Format) -> Result<(), Box<dyn Error>> {
+    sheet.set_column(0, 0, 25.0, None)?;
+    sheet.set_column(1, 2, 30.0, None)?;
+    sheet.set_column(3, 7, 15.0, None)?;
+
+    let headers = ["student_folder", "name", "email", "authored", "committed", "co_authored", "share", "matches_student"];
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string(0, col.try_into().unwrap(), header, Some(header_format))?;
+    }
+
+    let mut row = 1;
+    for submission in submissions.iter() {
+        let report = match &submission.authorship {
+            Some(r) => r,
+            None => continue,
+        };
+        for share in report.identities.iter() {
+            sheet.write_string(row, 0, &submission.student_folder, Some(header_format))?;
+            sheet.write_string(row, 1, &share.identity.name, None)?;
+            sheet.write_string(row, 2, &share.identity.email, None)?;
+            sheet.write_number(row, 3, share.authored as f64, None)?;
+            sheet.write_number(row, 4, share.committed as f64, None)?;
+            sheet.write_number(row, 5, share.co_authored as f64, None)?;
+            sheet.write_number(row, 6, share.share, None)?;
+            if let Some(matches) = share.matches_student {
+                sheet.write_boolean(row, 7, matches, None)?;
+            }
+            row += 1;
+        }
+    }
+    Ok(())
+}
+
+fn write_hygiene_sheet(sheet: &mut Worksheet, submissions: &[StudentProjectSubmission], header_format: &Format) -> Result<(), Box<dyn Error>> {
+    sheet.set_column(0, 0, 25.0, None)?;
+    sheet.set_column(1, 1, 15.0, None)?;
+    sheet.set_column(2, 2, 50.0, None)?;
+    sheet.set_column(3, 3, 15.0, None)?;
+    sheet.set_column(4, 4, 40.0, None)?;
+
+    let headers = ["student_folder", "kind", "path", "removed_commit", "message"];
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string(0, col.try_into().unwrap(), header, Some(header_format))?;
+    }
+
+    let mut row = 1;
+    for submission in submissions.iter() {
+        let findings = match &submission.hygiene {
+            Some(f) => f,
+            None => continue,
+        };
+        for finding in findings.iter() {
+            sheet.write_string(row, 0, &submission.student_folder, Some(header_format))?;
+            sheet.write_string(row, 1, &finding.kind, None)?;
+            sheet.write_string(row, 2, &finding.path, None)?;
+            if let Some(commit) = &finding.commit {
+                sheet.write_string(row, 3, commit, None)?;
+            }
+            sheet.write_string(row, 4, &finding.message, None)?;
+            row += 1;
+        }
+    }
+    Ok(())
+}
+
+fn write_timestamps_sheet(sheet: &mut Worksheet, submissions: &[StudentProjectSubmission], header_format: &Format) -> Result<(), Box<dyn Error>> {
+    sheet.set_column(0, 0, 25.0, None)?;
+    sheet.set_column(1, 2, 15.0, None)?;
+    sheet.set_column(3, 3, 80.0, None)?;
+
+    let headers = ["student_folder", "commit", "kind", "message"];
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string(0, col.try_into().unwrap(), header, Some(header_format))?;
+    }
+
+    let mut row = 1;
+    for submission in submissions.iter() {
+        let anomalies = match &submission.timestamp_anomalies {
+            Some(a) => a,
+            None => continue,
+        };
+        for anomaly in anomalies.iter() {
+            sheet.write_string(row, 0, &submission.student_folder, Some(header_format))?;
+            sheet.write_string(row, 1, &anomaly.commit, None)?;
+            sheet.write_string(row, 2, &anomaly.kind, None)?;
+            sheet.write_string(row, 3, &anomaly.message, None)?;
+            row += 1;
+        }
+    }
+    Ok(())
+}
+
+fn write_rules_sheet(sheet: &mut Worksheet, submissions: &[StudentProjectSubmission], header_format: &Format) -> Result<(), Box<dyn Error>> {
+    sheet.set_column(0, 0, 25.0, None)?;
+    sheet.set_column(1, 1, 10.0, None)?;
+    sheet.set_column(2, 3, 20.0, None)?;
+    sheet.set_column(4, 4, 10.0, None)?;
+    sheet.set_column(5, 5, 60.0, None)?;
+
+    let headers = ["student_folder", "task", "rule", "file", "line", "message"];
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string(0, col.try_into().unwrap(), header, Some(header_format))?;
+    }
+
+    let mut row = 1;
+    for submission in submissions.iter() {
+        let tasks = [
+            (&submission.has_task1, &submission.rule_violations_task1),
+            (&submission.has_task2, &submission.rule_violations_task2),
+        ];
+        for (task, violations) in tasks.iter() {
+            let (task, violations) = match (task, violations) {
+                (Some(task), Some(violations)) => (task, violations),
+                _ => continue,
+            };
+            for violation in violations.iter() {
+                sheet.write_string(row, 0, &submission.student_folder, Some(header_format))?;
+                sheet.write_string(row, 1, task, None)?;
+                sheet.write_string(row, 2, &violation.rule, None)?;
+                if let Some(file) = &violation.file {
+                    sheet.write_string(row, 3, file, None)?;
+                }
+                if let Some(line) = violation.line {
+                    sheet.write_number(row, 4, line as f64, None)?;
+                }
+                sheet.write_string(row, 5, &violation.message, None)?;
+                row += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_sanitizer_sheet(sheet: &mut Worksheet, submissions: &[StudentProjectSubmission], header_format: &Format) -> Result<(), Box<dyn Error>> {
+    sheet.set_column(0, 0, 25.0, None)?;
+    sheet.set_column(1, 1, 10.0, None)?;
+    sheet.set_column(2, 6, 25.0, None)?;
+
+    let headers = ["student_folder", "task", "kind", "file", "line", "function", "input"];
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string(0, col.try_into().unwrap(), header, Some(header_format))?;
+    }
+
+    let mut row = 1;
+    for submission in submissions.iter() {
+        let tasks = [
+            (&submission.has_task1, &submission.sanitizer_findings_task1),
+            (&submission.has_task2, &submission.sanitizer_findings_task2),
+        ];
+        for (task, findings) in tasks.iter() {
+            let (task, findings) = match (task, findings) {
+                (Some(task), Some(findings)) => (task, findings),
+                _ => continue,
+            };
+            for finding in findings.iter() {
+                sheet.write_string(row, 0, &submission.student_folder, Some(header_format))?;
+                sheet.write_string(row, 1, task, None)?;
+                sheet.write_string(row, 2, &finding.kind, None)?;
+                if let Some(file) = &finding.file {
+                    sheet.write_string(row, 3, file, None)?;
+                }
+                if let Some(line) = finding.line {
+                    sheet.write_number(row, 4, line as f64, None)?;
+                }
+                if let Some(function) = &finding.function {
+                    sheet.write_string(row, 5, function, None)?;
+                }
+                if let Some(input) = &finding.input {
+                    sheet.write_string(row, 6, input, None)?;
+                }
+                row += 1;
+            }
+        }
+    }
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,124 @@
+use std::fs;
+
+use crate::models::{student_project::StudentProjectSubmission, test_case::TestResult, rule_violation::RuleViolation, style_report::StyleReport, hygiene_finding::HygieneFinding};
+
+use super::git_compilation_handler::safe_name;
+
+// writes rp_workspace/feedback/<student_folder>.txt for every submission with
+// something to report. Hidden test cases are only counted, repo hygiene
+// findings get a section of their own after the tasks
+pub fn write_feedback(submissions: &[StudentProjectSubmission]) {
+    for submission in submissions.iter() {
+        let tasks = [
+            (&submission.has_task1, &submission.test_results_task1, &submission.rule_violations_task1, &submission.style_task1),
+            (&submission.has_task2, &submission.test_results_task2, &submission.rule_violations_task2, &submission.style_task2),
+        ];
+
+        let mut sections = vec![];
+        for (task, tests, violations, style) in tasks.iter() {
+            let task = match task {
+                Some(t) => t,
+                None => continue,
+            };
+            let mut section = String::new();
+            if let Some(tests) = tests {
+                section.push_str(&tests_feedback(tests));
+            }
+            if let Some(violations) = violations {
+                section.push_str(&rules_feedback(violations));
+            }
+            if let Some(style) = style {
+                section.push_str(&style_feedback(style));
+            }
+            if !section.is_empty() {
+                sections.push(format!("== {} ==\n{}", task, section));
+            }
+        }
+        if let Some(findings) = &submission.hygiene {
+            let section = hygiene_feedback(findings);
+            if !section.is_empty() {
+                sections.push(format!("== Repository ==\n{}", section));
+            }
+        }
+        if sections.is_empty() {
+            continue;
+        }
+
+        let content = format!("Feedback for {}\n\n{}", submission.student_folder, sections.join("\n"));
+        let path = format!("./rp_workspace/feedback/{}.txt", safe_name(&submission.student_folder));
+        if let Err(e) = fs::write(&path, content) {
+            println!("[FEEDBACK] Error writing feedback ({}): {:#?}", path, e);
+        }
+    }
+}
+
+fn tests_feedback(results: &[TestResult]) -> String {
+    let mut text = format!(
+        "-- Test cases: {} of {} passed --\n",
+        results.iter().filter(|r| r.passed).count(),
+        results.len()
+    );
+    for result in results.iter().filter(|r| !r.hidden) {
+        let verdict = if result.passed { "PASS" } else { "FAIL" };
+        match &result.message {
+            Some(message) => text.push_str(&format!("[{}] {}: {}\n", verdict, result.name, message)),
+            None => text.push_str(&format!("[{}] {}\n", verdict, result.name)),
+        }
+    }
+    let hidden = results.iter().filter(|r| r.hidden).count();
+    if hidden > 0 {
+        text.push_str(&format!("{} hidden test cases are not listed\n", hidden));
+    }
+    text.push('\n');
+    text
+}
+
+fn rules_feedback(violations: &[RuleViolation]) -> String {
+    if violations.is_empty() {
+        return String::new();
+    }
+    let mut text = format!("-- Rule violations: {} --\n", violations.len());
+    for violation in violations.iter() {
+        let location = match (&violation.file, violation.line) {
+            (Some(file), Some(line)) => format!("{}:{}: ", file, line),
+            (Some(file), None) => format!("{}: ", file),
+            _ => "".to_string(),
+        };
+        text.push_str(&format!("{}[{}] {}\n", location, violation.rule, violation.message));
+    }
+    text.push('\n');
+    text
+}
+
+fn style_feedback(report: &StyleReport) -> String {
+    if report.issues.is_empty() && report.diff.is_empty() {
+        return String::new();
+    }
+    let mut text = format!("-- Style: {} deviating lines --\n", report.deviating_lines);
+    for issue in report.issues.iter() {
+        text.push_str(&format!("{}:{}: {}\n", issue.file, issue.line, issue.message));
+    }
+    if !report.diff.is_empty() {
+        text.push_str("\nSuggested formatting:\n");
+        text.push_str(&report.diff);
+    }
+    text.push('\n');
+    text
+}
+
+fn hygiene_feedback(findings: &[HygieneFinding]) -> String {
+    if findings.is_empty() {
+        return String::new();
+    }
+    let mut text = format!("-- Hygiene: {} findings --\n", findings.len());
+    for finding in findings.iter() {
+        let location = match (finding.path.as_str(), &finding.commit) {
+            ("", _) => "".to_string(),
+            (path, Some(commit)) => format!("{} (added in {}, removed since): ", path, commit),
+            (path, None) => format!("{}: ", path),
+        };
+        text.push_str(&format!("{}[{}] {}\n", location, finding.kind, finding.message));
+    }
+    text.push('\n');
+    text
+}
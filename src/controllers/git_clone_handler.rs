@@ -1,8 +1,12 @@
-use crate::models::{student_project::StudentProjectSubmission, file_path::FilePath};
-use super::{validator::{check_dir_exists}, os_helper::{run_command, folder_names}, parser::escape};
+use std::{collections::HashMap, io::ErrorKind};
 
+use chrono::Utc;
 
-pub fn clone_repos(submissions: &mut Vec<StudentProjectSubmission>, sources: &Vec<FilePath>) {
+use crate::models::{student_project::StudentProjectSubmission, file_path::FilePath, config::AssignmentConfig};
+use super::{validator::{check_dir_exists}, os_helper::{run_command, run_command_with_limits, folder_names}, parser::{escape, normalize_repo_url}};
+
+
+pub fn clone_repos(submissions: &mut Vec<StudentProjectSubmission>, sources: &Vec<FilePath>, config: &AssignmentConfig) {
     if let false = check_dir_exists("rp_workspace/sources") {
         println!("[GIT HANDLER] Error reading sources directory!");
         std::process::exit(1);
@@ -12,15 +16,43 @@ pub fn clone_repos(submissions: &mut Vec<StudentProjectSubmission>, sources: &Ve
         std::process::exit(1);
     }
 
+    find_duplicate_repos(submissions);
+    // normalized url => folder of the first clone, shared by the other submissions
+    let mut clones: HashMap<String, String> = HashMap::new();
+
     for submission in submissions.iter_mut() {
         // if submission has a repo
         if let Some(repo) = &submission.git_repo {
+            let normalized = normalize_repo_url(repo);
+            if let Some(first) = clones.get(&normalized) {
+                match run_command(&format!(
+                    "cp -r ./rp_workspace/repos/{} ./rp_workspace/repos/{}",
+                    escape(first),
+                    escape(&submission.student_folder)
+                )) {
+                    Ok(_) => {
+                        submission.cloned = true;
+                        submission.cloned_at = Some(Utc::now());
+                        continue;
+                    },
+                    Err(e) => println!("[GIT HANDLER] Error copying the clone of ({}), cloning it again:\n{:#?}", first, e),
+                }
+            }
+
             // try to clone it
-            if let Err(e) = run_command(format!("git clone {} ./rp_workspace/repos/{}", repo, escape(&submission.student_folder)).as_str()) {
+            if let Err(e) = run_command_with_limits(
+                format!("git clone {} ./rp_workspace/repos/{}", repo, escape(&submission.student_folder)).as_str(),
+                &config.clone_limits
+            ) {
                 println!("[GIT HANDLER] Error cloning git repo({}):\n{:#?}",repo, e);
+                if e.kind() == ErrorKind::TimedOut {
+                    submission.timeouts.push("clone".to_string());
+                }
                 continue;
             }; 
             submission.cloned = true;
+            submission.cloned_at = Some(Utc::now());
+            clones.insert(normalized, submission.student_folder.clone());
         }
     }
 
@@ -36,7 +68,10 @@ pub fn clone_repos(submissions: &mut Vec<StudentProjectSubmission>, sources: &Ve
 
     if !jordan_link.is_empty() {
         // clone master repo
-        if let Err(e) = run_command(format!("git clone {} ./rp_workspace/repos/jrdndj/", jordan_link).as_str()) {
+        if let Err(e) = run_command_with_limits(
+            format!("git clone {} ./rp_workspace/repos/jrdndj/", jordan_link).as_str(),
+            &config.clone_limits
+        ) {
             println!("[GIT HANDLER] Error cloning git repo({}):\n{:#?}",jordan_link, e);
         }; 
 
@@ -54,7 +89,30 @@ pub fn clone_repos(submissions: &mut Vec<StudentProjectSubmission>, sources: &Ve
                 true
             );
             submission.cloned = true;
+            submission.cloned_at = Some(Utc::now());
             submissions.push(submission);
         }
     }
 }
+
+// records on every submission the other submissions that point at the same repo
+fn find_duplicate_repos(submissions: &mut [StudentProjectSubmission]) {
+    let mut folders: HashMap<String, Vec<String>> = HashMap::new();
+    for submission in submissions.iter() {
+        if let Some(repo) = &submission.git_repo {
+            folders.entry(normalize_repo_url(repo)).or_default().push(submission.student_folder.clone());
+        }
+    }
+
+    for submission in submissions.iter_mut() {
+        let same_repo = match submission.git_repo.as_ref().and_then(|r| folders.get(&normalize_repo_url(r))) {
+            Some(f) => f,
+            None => continue,
+        };
+        submission.same_repo_as = same_repo
+            .iter()
+            .filter(|f| **f != submission.student_folder)
+            .cloned()
+            .collect();
+    }
+}
@@ -1,8 +1,11 @@
-use crate::models::student_project::StudentProjectSubmission;
+use std::{collections::{BTreeMap, HashMap}, io::Error};
+
+use crate::models::{student_project::StudentProjectSubmission, config::{AssignmentConfig, TimestampConfig}, commit_info::{CommitInfo, Identity}, timestamp_anomaly::TimestampAnomaly};
 use chrono::{NaiveDateTime, Utc, DateTime};
+use regex::Regex;
 use super::parser::escape;
 
-use super::{validator::{check_dir_exists, find_main_file, find_accepted_folder, tasks_to_check}, os_helper::run_command};
+use super::{validator::{check_dir_exists, find_accepted_folder, tasks_to_check}, os_helper::run_command, languages::get_language};
 
 
 pub fn get_commits_from_submission(task: &str, submission: &mut StudentProjectSubmission) -> Option<Vec<String>> {
@@ -75,7 +78,7 @@ pub fn extract_commits(submissions: &mut [StudentProjectSubmission]) {
     }
 }
 
-fn save_commits_to_submission(submission: &mut StudentProjectSubmission, task: &str, commits: Option<Vec<String>>) {
+pub fn save_commits_to_submission(submission: &mut StudentProjectSubmission, task: &str, commits: Option<Vec<String>>) {
     if let Some(task1) = submission.has_task1.clone() {
         if task1 == task {
             submission.commits_task1 = commits;
@@ -90,39 +93,43 @@ fn save_commits_to_submission(submission: &mut StudentProjectSubmission, task: &
     }
 }
 
-pub fn check_structure(submissions: &mut [StudentProjectSubmission]) {
+pub const ACCEPTED_FOLDER_NAMES_TASK1: [&str; 8] = ["Task1", "task1", "1task", "1Task", "task_1", "Task_1", "Task 1", "task 1"];
+pub const ACCEPTED_FOLDER_NAMES_TASK2: [&str; 8] = ["Task2", "task2", "2task", "2Task", "task_2", "Task_2", "Task 2", "task 2"];
+
+pub fn check_structure(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
     if let false = check_dir_exists("rp_workspace/repos") {
         println!("[GIT HANDLER] Error reading repos directory!");
         std::process::exit(1);
     }
 
     for submission in submissions.iter_mut() {
-        if !submission.cloned {
+        // without a task folder every task stage skips the submission
+        if !submission.cloned || submission.no_commit_before_deadline {
             continue;
         }
 
-        let accepted_folder_names_task1 = ["Task1", "task1", "1task", "1Task", "task_1", "Task_1", "Task 1", "task 1"];
-        let accepted_folder_names_task2 = ["Task2", "task2", "2task", "2Task", "task_2", "Task_2", "Task 2", "task 2"];
-    
         submission.has_task1 = find_accepted_folder(
             &format!("./rp_workspace/repos/{}", submission.student_folder), 
-            &accepted_folder_names_task1
+            &ACCEPTED_FOLDER_NAMES_TASK1
         );
         
         submission.has_task2 = find_accepted_folder(
             &format!("./rp_workspace/repos/{}", submission.student_folder), 
-            &accepted_folder_names_task2
+            &ACCEPTED_FOLDER_NAMES_TASK2
         );
 
+        // languages are validated when loading the config
+        let language_task1 = get_language(&config.task1.language).unwrap();
+        let language_task2 = get_language(&config.task2.language).unwrap();
         if let Some(task) = &submission.has_task1 {
-            submission.task1_main = find_main_file(&format!(
+            submission.task1_main = language_task1.find_main_file(&format!(
                 "./rp_workspace/repos/{}/{}", 
                 submission.student_folder, 
                 task
             ));
         }
         if let Some(task) = &submission.has_task2 {
-            submission.task2_main = find_main_file(&format!(
+            submission.task2_main = language_task2.find_main_file(&format!(
                 "./rp_workspace/repos/{}/{}", 
                 submission.student_folder, 
                 task
@@ -158,4 +165,134 @@ pub fn check_latest_commit_date(submissions: &mut [StudentProjectSubmission]) {
             submission.last_commit_date = NaiveDateTime::parse_from_str(line, "%a %b %d %H:%M:%S %Y %z").ok();
         }
     }
-}
\ No newline at end of file
+}
+
+// reads every commit reachable from the revision and flags suspicious timestamps
+pub fn check_commit_timestamps(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[GIT HANDLER] Error reading repos directory!");
+        std::process::exit(1);
+    }
+
+    for submission in submissions.iter_mut() {
+        if !submission.cloned {
+            continue;
+        }
+        // with a deadline snapshot checked out, HEAD misses the late commits
+        let revision = submission.head_commit.clone().unwrap_or("HEAD".to_string());
+        let commits = match read_commit_infos(&submission.student_folder, &revision, submission.jordan) {
+            Ok(c) => c,
+            Err(e) => {
+                println!("[GIT HANDLER] Error reading commit dates of repo ({}): {:#?}", submission.student_folder, e);
+                continue;
+            },
+        };
+        let cloned_at = submission.cloned_at.unwrap_or_else(Utc::now);
+        submission.timestamp_anomalies = Some(find_timestamp_anomalies(&commits, cloned_at, &config.timestamp_config));
+        submission.commit_infos = commits;
+    }
+}
+
+// jordan's submissions share one repo, only the commits touching the folder count
+fn read_commit_infos(student_folder: &str, revision: &str, jordan: bool) -> Result<Vec<CommitInfo>, Error> {
+    // fields are separated by 0x1f and commits by 0x1e, messages span lines
+    let output = run_command(&format!(
+        "git -C ./rp_workspace/repos/{} --no-pager log --format=\"%h%x1f%p%x1f%aI%x1f%cI%x1f%an%x1f%ae%x1f%cn%x1f%ce%x1f%B%x1e\" {}{}",
+        escape(student_folder),
+        revision,
+        if jordan { " -- ." } else { "" }
+    ))?;
+    let co_author = Regex::new(r"(?im)^co-authored-by:\s*(.*?)\s*<([^>]*)>").unwrap();
+
+    let mut commits = vec![];
+    for record in output.split('\x1e') {
+        let fields: Vec<&str> = record.trim_start_matches('\n').split('\x1f').collect();
+        if fields.len() != 9 {
+            continue;
+        }
+        let (author_date, committer_date) = match (
+            DateTime::parse_from_rfc3339(fields[2]),
+            DateTime::parse_from_rfc3339(fields[3]),
+        ) {
+            (Ok(a), Ok(c)) => (a, c),
+            _ => {
+                println!("[GIT HANDLER] Error parsing dates of commit ({}) in repo ({})", fields[0], student_folder);
+                continue;
+            },
+        };
+        commits.push(CommitInfo {
+            hash: fields[0].to_string(),
+            parents: fields[1].split_whitespace().map(|p| p.to_string()).collect(),
+            author_date,
+            committer_date,
+            author: Identity { name: fields[4].to_string(), email: fields[5].to_string() },
+            committer: Identity { name: fields[6].to_string(), email: fields[7].to_string() },
+            co_authors: co_author
+                .captures_iter(fields[8])
+                .map(|c| Identity { name: c[1].to_string(), email: c[2].to_string() })
+                .collect(),
+        });
+    }
+    Ok(commits)
+}
+
+fn find_timestamp_anomalies(commits: &[CommitInfo], cloned_at: DateTime<Utc>, config: &TimestampConfig) -> Vec<TimestampAnomaly> {
+    let by_hash: HashMap<&str, &CommitInfo> = commits.iter().map(|c| (c.hash.as_str(), c)).collect();
+    let mut anomalies = vec![];
+    let mut anomaly = |commit: &CommitInfo, kind: &str, message: String| {
+        anomalies.push(TimestampAnomaly {
+            commit: commit.hash.clone(),
+            kind: kind.to_string(),
+            message,
+        });
+    };
+
+    for commit in commits.iter() {
+        for parent in commit.parents.iter().filter_map(|p| by_hash.get(p.as_str())) {
+            if commit.committer_date < parent.committer_date {
+                anomaly(commit, "parent_order", format!(
+                    "committed {} before its parent {} ({})",
+                    commit.committer_date, parent.hash, parent.committer_date
+                ));
+            }
+        }
+
+        if let Some(parent) = commit.parents.first().and_then(|p| by_hash.get(p.as_str())) {
+            let jump = commit.committer_date.offset().local_minus_utc() - parent.committer_date.offset().local_minus_utc();
+            if i64::from(jump.abs()) > config.max_timezone_jump_hours * 3600 {
+                anomaly(commit, "timezone_jump", format!(
+                    "committed in UTC{} after {} in UTC{}",
+                    commit.committer_date.offset(), parent.hash, parent.committer_date.offset()
+                ));
+            }
+        }
+
+        let gap = (commit.committer_date - commit.author_date).num_hours().abs();
+        if gap > config.max_author_gap_hours {
+            anomaly(commit, "author_gap", format!(
+                "authored {} but committed {} ({} hours apart)",
+                commit.author_date, commit.committer_date, gap
+            ));
+        }
+
+        if commit.author_date > cloned_at || commit.committer_date > cloned_at {
+            anomaly(commit, "future", format!(
+                "dated {} after the repo was cloned ({})",
+                commit.committer_date.max(commit.author_date), cloned_at
+            ));
+        }
+    }
+
+    // committer dates shared by too many commits, reported on the newest of them
+    let mut same_date: BTreeMap<i64, Vec<&CommitInfo>> = BTreeMap::new();
+    for commit in commits.iter() {
+        same_date.entry(commit.committer_date.timestamp()).or_default().push(commit);
+    }
+    for group in same_date.values().filter(|g| g.len() > config.max_identical) {
+        anomaly(group[0], "identical", format!(
+            "{} commits share the committer date {}",
+            group.len(), group[0].committer_date
+        ));
+    }
+    anomalies
+}
@@ -1,9 +1,13 @@
-use crate::{models::student_project::StudentProjectSubmission, controllers::parser::escape};
+use std::{env, io::{Error, ErrorKind}};
 
-use super::{validator::{check_dir_exists, tasks_to_check}, git_commit_handler::get_commits_from_submission, os_helper::run_command};
+use regex::Regex;
 
+use crate::{models::{student_project::StudentProjectSubmission, standard_report::{StandardReport, StandardConstruct}, config::{AssignmentConfig, ResourceLimits}}, controllers::parser::escape};
 
-pub fn compile_commits(submissions: &mut [StudentProjectSubmission]) {
+use super::{validator::{check_dir_exists, tasks_to_check}, git_commit_handler::get_commits_from_submission, os_helper::{run_command, run_command_with_limits}, config_handler::get_task_language, languages::Language};
+
+
+pub fn compile_commits(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
     if let false = check_dir_exists("rp_workspace/repos") {
         println!("[GIT HANDLER] Error reading repos directory!");
         std::process::exit(1);
@@ -23,6 +27,8 @@ pub fn compile_commits(submissions: &mut [StudentProjectSubmission]) {
                 Some(main) => main,
                 None => continue,
             };
+            let language = get_task_language(config, submission, task);
+            let task_path = format!("./rp_workspace/repos/{}/{}", escape(&student_folder), escape(task));
             // fetch commits of submission
             // if there are none, continue to next task/submisssion
             let commits  =  match get_commits_from_submission(task, submission) {
@@ -56,26 +62,25 @@ pub fn compile_commits(submissions: &mut [StudentProjectSubmission]) {
                 }; 
 
 
-                // compile with gcc
-                let standards = ["c99", "c90", "c89", "c11", "c17"];
+                // compile with the compiler of the task language
                 let mut command_output = "".to_string();
                 
-                for standard in standards.iter() {
-                    match run_command(format!(
-                        "gcc -std={} ./rp_workspace/repos/{}/{}/{}", 
-                        standard, 
-                        escape(&student_folder), 
-                        escape(&task), 
-                        escape(&task_main_file)
-                    ).as_str()) {
+                for check_command in language.check_commands(&task_path, &task_main_file).iter() {
+                    match run_command_with_limits(check_command, &language.limits(&config.compile_limits)) {
                         Ok(t) => {
                             command_output = t;
                             if command_output.is_empty() {
-                                submission.gcc_standard = Some(standard.to_string());
                                 break;
                             }
                         },
-                        Err(e) => command_output = e.to_string(),
+                        Err(e) => {
+                            command_output = e.to_string();
+                            // other variants would hang the same way
+                            if e.kind() == ErrorKind::TimedOut {
+                                submission.timeouts.push(format!("compile {}@{}", task, commit_string));
+                                break;
+                            }
+                        },
                     }; 
                 }
 
@@ -106,7 +111,170 @@ pub fn compile_commits(submissions: &mut [StudentProjectSubmission]) {
     }
 }
 
-fn get_submission_main_file(submission: &mut StudentProjectSubmission, task: &str) -> Option<String> {
+pub fn check_standards(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[GIT HANDLER] Error reading repos directory!");
+        std::process::exit(1);
+    }
+
+    for submission in submissions.iter_mut() {
+        if !submission.cloned {
+            continue;
+        }
+
+        for task in tasks_to_check(submission).iter() {
+            // standards only make sense for C
+            if get_task_language(config, submission, task).name() != "c" {
+                continue;
+            }
+            let task_main_file = match get_submission_main_file(submission, task) {
+                Some(main) => main,
+                None => continue,
+            };
+            // repo is checked out on the latest commit, so this is the final
+            // state of the task
+            let report = analyze_standards(&format!(
+                "./rp_workspace/repos/{}/{}/{}",
+                escape(&submission.student_folder),
+                escape(task),
+                escape(&task_main_file)
+            ), &config.compile_limits);
+            match report {
+                Ok(report) => save_standard_report_to_submission(submission, task, report),
+                Err(e) => {
+                    println!("[GIT HANDLER] Error checking standards of task ({}): {:#?}", task, e);
+                    submission.timeouts.push(format!("standards {}", task));
+                },
+            }
+        }
+    }
+}
+
+// only fails if gcc exceeds the limits
+fn analyze_standards(main_file_path: &str, limits: &ResourceLimits) -> Result<StandardReport, Error> {
+    // (name, gcc flag) from oldest to newest
+    let standards = [("c89", "c89"), ("c99", "c99"), ("c11", "c11"), ("c17", "c17"), ("c23", "c2x")];
+    let diagnostic = Regex::new(r"^[^:]+:(\d+):(?:\d+:)? (?:fatal )?(?:warning|error): (.*)$").unwrap();
+
+    let mut clean_standards = vec![];
+    let mut diagnostics = vec![];
+    for (name, flag) in standards.iter() {
+        // "|| true" so the diagnostics end up in the output even if gcc fails
+        let command_output = match run_command_with_limits(format!(
+            "gcc -std={} -pedantic -fsyntax-only {} 2>&1 || true",
+            flag,
+            main_file_path
+        ).as_str(), limits) {
+            Ok(t) => t,
+            Err(e) if e.kind() == ErrorKind::TimedOut => return Err(e),
+            Err(e) => {
+                println!("[GIT HANDLER] Error checking standard {} on ({}): {:#?}", name, main_file_path, e);
+                continue;
+            },
+        };
+
+        if command_output.is_empty() {
+            clean_standards.push(name.to_string());
+            continue;
+        }
+
+        for line in command_output.lines() {
+            if let Some(capture) = diagnostic.captures(line) {
+                diagnostics.push(StandardConstruct {
+                    standard: name.to_string(),
+                    line: capture[1].parse().ok(),
+                    message: capture[2].to_string(),
+                });
+            }
+        }
+    }
+
+    let minimum_standard = clean_standards.first().cloned();
+    let mut constructs: Vec<StandardConstruct> = vec![];
+    if let Some(minimum) = &minimum_standard {
+        // only the standards older than the minimum are rejected because of
+        // the constructs used
+        let older: Vec<&str> = standards
+            .iter()
+            .map(|(name, _)| *name)
+            .take_while(|name| name != minimum)
+            .collect();
+        for construct in diagnostics.into_iter() {
+            if !older.contains(&construct.standard.as_str()) {
+                continue;
+            }
+            if constructs.iter().any(|c| c.line == construct.line && c.message == construct.message) {
+                continue;
+            }
+            constructs.push(construct);
+        }
+    }
+
+    Ok(StandardReport {
+        clean_standards,
+        minimum_standard,
+        constructs,
+    })
+}
+
+fn save_standard_report_to_submission(submission: &mut StudentProjectSubmission, task: &str, report: StandardReport) {
+    if let Some(task1) = submission.has_task1.clone() {
+        if task1 == task {
+            submission.standards_task1 = Some(report);
+            return;
+        }
+    }
+
+    if let Some(task2) = submission.has_task2.clone() {
+        if task2 == task {
+            submission.standards_task2 = Some(report);
+        }
+    }
+}
+
+// builds the task into rp_workspace/bin and returns the command that runs
+// the build
+pub fn build_task(
+    language: &dyn Language,
+    student_folder: &str, 
+    task: &str, 
+    main_file: &str, 
+    flags: &str, 
+    suffix: &str, 
+    limits: &ResourceLimits,
+) -> Result<String, Error> {
+    let output = binary_path(student_folder, task, suffix);
+    let task_path = format!("./rp_workspace/repos/{}/{}", escape(student_folder), escape(task));
+    run_command_with_limits(&language.build_command(&task_path, main_file, &output, flags), limits)?;
+
+    // the run command has to work from the working directory of a test run
+    let root = escape(&env::current_dir()?.to_string_lossy());
+    Ok(language.run_command(
+        &format!("{}/{}", root, task_path.trim_start_matches("./")),
+        main_file,
+        &format!("{}/{}", root, output.trim_start_matches("./")),
+    ))
+}
+
+// unique path in rp_workspace/bin, safe to use in shell commands
+pub fn binary_path(student_folder: &str, task: &str, suffix: &str) -> String {
+    format!("./rp_workspace/bin/{}", workspace_name(student_folder, task, suffix))
+}
+
+// unique file name for the task of a student
+pub fn workspace_name(student_folder: &str, task: &str, suffix: &str) -> String {
+    safe_name(&format!("{}_{}_{}", student_folder, task, suffix))
+}
+
+// a single file name, jordan's folders contain slashes and spaces
+pub fn safe_name(name: &str) -> String {
+    name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect()
+}
+
+pub fn get_submission_main_file(submission: &StudentProjectSubmission, task: &str) -> Option<String> {
     if let Some(task1) = submission.has_task1.clone() {
         if task1 == task {
             return submission.task1_main.clone();
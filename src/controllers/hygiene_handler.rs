@@ -0,0 +1,300 @@
+use std::{collections::{HashMap, HashSet}, fs::File, io::{Error, Read}};
+
+use regex::Regex;
+
+use crate::models::{student_project::StudentProjectSubmission, config::{AssignmentConfig, HygieneConfig}, hygiene_finding::HygieneFinding};
+
+use super::{
+    validator::check_dir_exists,
+    os_helper::run_command,
+    parser::escape,
+};
+
+
+#[derive(Clone)]
+struct TreeEntry {
+    path: String,
+    is_dir: bool,
+    // bytes, 0 for folders
+    size: u64,
+    // commit that added it, only read from the history
+    commit: Option<String>,
+}
+
+struct RepoFiles {
+    head: Vec<TreeEntry>,
+    // None without history checks or if it could not be read
+    history: Option<Vec<TreeEntry>>,
+}
+
+struct Patterns {
+    binaries: Vec<Regex>,
+    ide_folders: Vec<Regex>,
+    junk: Vec<Regex>,
+}
+
+// flags build artifacts, IDE folders, OS junk and large files in the judged
+// commit, and with history enabled also those committed and removed again
+pub fn check_hygiene(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[HYGIENE] Error reading repos directory!");
+        std::process::exit(1);
+    }
+
+    let patterns = Patterns {
+        binaries: compile_patterns(&config.hygiene.binaries),
+        ide_folders: compile_patterns(&config.hygiene.ide_folders),
+        junk: compile_patterns(&config.hygiene.junk),
+    };
+
+    // jordan's submissions share one repo, it is read once and each student is
+    // checked on their own folder
+    let mut jordan_files: Option<Option<RepoFiles>> = None;
+    for submission in submissions.iter_mut() {
+        if !submission.cloned || submission.no_commit_before_deadline {
+            continue;
+        }
+        let worktree = format!("./rp_workspace/repos/{}", submission.student_folder);
+
+        let findings = match submission.student_folder.strip_prefix("jrdndj/").filter(|_| submission.jordan) {
+            Some(folder) => {
+                let files = match jordan_files.get_or_insert_with(|| read_repo_files("jrdndj", &config.hygiene)) {
+                    Some(f) => f,
+                    None => continue,
+                };
+                let head = in_folder(&files.head, folder);
+                let history = files.history.as_ref().map(|h| in_folder(h, folder));
+                // the .gitignore of the shared repo counts for every folder
+                let has_gitignore = has_gitignore(&files.head) || has_gitignore(&head);
+                repo_findings(&head, history.as_deref(), has_gitignore, &worktree, &patterns, &config.hygiene)
+            },
+            None => match read_repo_files(&submission.student_folder, &config.hygiene) {
+                Some(files) => repo_findings(&files.head, files.history.as_deref(), has_gitignore(&files.head), &worktree, &patterns, &config.hygiene),
+                None => continue,
+            },
+        };
+
+        save_hygiene_to_submission(submission, findings);
+    }
+}
+
+// the judged tree and, with history enabled, every path ever added
+fn read_repo_files(repo: &str, config: &HygieneConfig) -> Option<RepoFiles> {
+    let head = match read_head_tree(&escape(repo)) {
+        Ok(h) => h,
+        Err(e) => {
+            println!("[HYGIENE] Error listing files of repo ({}): {:#?}", repo, e);
+            return None;
+        },
+    };
+    let history = match config.history {
+        true => match read_history(&escape(repo)) {
+            Ok(h) => Some(h),
+            Err(e) => {
+                println!("[HYGIENE] Error reading history of repo ({}): {:#?}", repo, e);
+                None
+            },
+        },
+        false => None,
+    };
+    Some(RepoFiles { head, history })
+}
+
+// entries below folder, with paths relative to it
+fn in_folder(entries: &[TreeEntry], folder: &str) -> Vec<TreeEntry> {
+    let prefix = format!("{}/", folder);
+    entries
+        .iter()
+        .filter_map(|e| Some(TreeEntry { path: e.path.strip_prefix(&prefix)?.to_string(), ..e.clone() }))
+        .collect()
+}
+
+fn has_gitignore(head: &[TreeEntry]) -> bool {
+    head.iter().any(|e| e.path == ".gitignore")
+}
+
+// paths of head and history are relative to worktree
+fn repo_findings(
+    head: &[TreeEntry],
+    history: Option<&[TreeEntry]>,
+    has_gitignore: bool,
+    worktree: &str,
+    patterns: &Patterns,
+    config: &HygieneConfig,
+) -> Vec<HygieneFinding> {
+    let mut findings = find_violations(head, patterns, config, Some(worktree));
+
+    if config.require_gitignore && !has_gitignore {
+        findings.push(HygieneFinding {
+            kind: "no_gitignore".to_string(),
+            path: "".to_string(),
+            commit: None,
+            message: "no .gitignore at the repo root".to_string(),
+        });
+    }
+
+    if let Some(history) = history {
+        let at_head: HashSet<&str> = head.iter().map(|e| e.path.as_str()).collect();
+        let removed: Vec<TreeEntry> = history.iter().filter(|e| !at_head.contains(e.path.as_str())).cloned().collect();
+        findings.extend(find_violations(&removed, patterns, config, None));
+    }
+    findings
+}
+
+fn save_hygiene_to_submission(submission: &mut StudentProjectSubmission, findings: Vec<HygieneFinding>) {
+    submission.hygiene = Some(findings);
+}
+
+// '*' matches any run of characters, everything else literally
+fn compile_patterns(patterns: &[String]) -> Vec<Regex> {
+    patterns
+        .iter()
+        .filter_map(|p| Regex::new(&format!("^{}$", regex::escape(p).replace("\\*", ".*"))).ok())
+        .collect()
+}
+
+fn matches(patterns: &[Regex], path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    patterns.iter().any(|p| p.is_match(name))
+}
+
+// the contents of a flagged folder are not flagged one by one, worktree is the
+// checkout to look for executables in
+fn find_violations(entries: &[TreeEntry], patterns: &Patterns, config: &HygieneConfig, worktree: Option<&str>) -> Vec<HygieneFinding> {
+    let mut findings = vec![];
+    let mut flagged_folders: Vec<String> = vec![];
+    for entry in entries.iter() {
+        if flagged_folders.iter().any(|f| entry.path.starts_with(&format!("{}/", f))) {
+            continue;
+        }
+
+        let flag = if entry.is_dir && matches(&patterns.ide_folders, &entry.path) {
+            Some(("ide_folder", "IDE folder".to_string()))
+        } else if matches(&patterns.junk, &entry.path) {
+            Some(("junk", "OS junk".to_string()))
+        } else if entry.is_dir {
+            None
+        } else if matches(&patterns.binaries, &entry.path) {
+            Some(("binary", "build artifact".to_string()))
+        } else if let Some(format) = worktree.filter(|_| config.detect_executables).and_then(|w| executable_format(&format!("{}/{}", w, entry.path))) {
+            Some(("binary", format!("{} executable", format)))
+        } else if config.max_file_kb > 0 && entry.size > config.max_file_kb * 1024 {
+            Some(("large_file", format!("{} KB, more than {} KB", entry.size.div_ceil(1024), config.max_file_kb)))
+        } else {
+            None
+        };
+
+        if let Some((kind, message)) = flag {
+            if entry.is_dir {
+                flagged_folders.push(entry.path.clone());
+            }
+            findings.push(HygieneFinding {
+                kind: kind.to_string(),
+                path: entry.path.clone(),
+                commit: entry.commit.clone(),
+                message,
+            });
+        }
+    }
+    findings
+}
+
+fn executable_format(path: &str) -> Option<&'static str> {
+    let mut magic = [0u8; 4];
+    File::open(path).ok()?.read_exact(&mut magic).ok()?;
+    match magic {
+        [0x7f, b'E', b'L', b'F'] => Some("ELF"),
+        [0xfe, 0xed, 0xfa, 0xce | 0xcf] | [0xce | 0xcf, 0xfa, 0xed, 0xfe] => Some("Mach-O"),
+        [b'M', b'Z', _, _] => Some("PE"),
+        _ => None,
+    }
+}
+
+// files and folders of the judged commit, folders before their contents
+fn read_head_tree(repo: &str) -> Result<Vec<TreeEntry>, Error> {
+    let output = run_command(&format!("git -C ./rp_workspace/repos/{} ls-tree -r -t -l -z HEAD", repo))?;
+    let mut entries = vec![];
+    for line in output.split('\0') {
+        let (meta, path) = match line.split_once('\t') {
+            Some(l) => l,
+            None => continue,
+        };
+        let fields: Vec<&str> = meta.split_whitespace().collect();
+        // submodules and symlinks have nothing to check
+        if fields.len() != 4 || fields[0] == "120000" || fields[1] == "commit" {
+            continue;
+        }
+        entries.push(TreeEntry {
+            path: path.to_string(),
+            is_dir: fields[1] == "tree",
+            size: fields[3].parse().unwrap_or(0),
+            commit: None,
+        });
+    }
+    Ok(entries)
+}
+
+// every path ever added on any branch with the oldest commit adding it,
+// folders before their contents
+fn read_history(repo: &str) -> Result<Vec<TreeEntry>, Error> {
+    let sizes: HashMap<String, u64> = run_command(&format!(
+        "git -C ./rp_workspace/repos/{} cat-file --batch-all-objects --batch-check=\"%(objectname) %(objectsize)\"",
+        repo
+    ))?
+        .lines()
+        .filter_map(|l| l.split_once(' '))
+        .map(|(hash, size)| (hash.to_string(), size.parse().unwrap_or(0)))
+        .collect();
+    // --no-abbrev also lengthens %h, the short ids are read on their own
+    let short: HashMap<String, String> = run_command(&format!(
+        "git -C ./rp_workspace/repos/{} --no-pager log --all --format=\"%H %h\"",
+        repo
+    ))?
+        .lines()
+        .filter_map(|l| l.split_once(' '))
+        .map(|(hash, short)| (hash.to_string(), short.to_string()))
+        .collect();
+    let output = run_command(&format!(
+        "git -C ./rp_workspace/repos/{} -c core.quotePath=false --no-pager log --all --reverse --diff-filter=A --no-renames --raw --no-abbrev --format=%x1e%H",
+        repo
+    ))?;
+
+    let mut seen = HashSet::new();
+    let mut entries = vec![];
+    for commit in output.split('\x1e').skip(1) {
+        let mut lines = commit.lines();
+        let hash = lines.next().unwrap_or("").trim();
+        let hash = short.get(hash).cloned().unwrap_or(hash.to_string());
+        // :000000 100644 0000000... <blob> A\tpath
+        for line in lines {
+            let (meta, path) = match line.split_once('\t') {
+                Some(l) => l,
+                None => continue,
+            };
+            let fields: Vec<&str> = meta.split_whitespace().collect();
+            if fields.len() != 5 || fields[1] == "120000" || fields[1] == "160000" {
+                continue;
+            }
+            let folders = path.match_indices('/').map(|(i, _)| &path[..i]);
+            for folder in folders {
+                if seen.insert(folder.to_string()) {
+                    entries.push(TreeEntry {
+                        path: folder.to_string(),
+                        is_dir: true,
+                        size: 0,
+                        commit: Some(hash.clone()),
+                    });
+                }
+            }
+            if seen.insert(path.to_string()) {
+                entries.push(TreeEntry {
+                    path: path.to_string(),
+                    is_dir: false,
+                    size: sizes.get(fields[3]).copied().unwrap_or(0),
+                    commit: Some(hash.clone()),
+                });
+            }
+        }
+    }
+    Ok(entries)
+}
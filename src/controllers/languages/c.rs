@@ -0,0 +1,52 @@
+use crate::controllers::parser::escape;
+
+use super::{Language, find_file_by_extension, find_files_by_extension};
+
+pub struct C;
+
+impl Language for C {
+    fn name(&self) -> &'static str {
+        "c"
+    }
+
+    fn find_main_file(&self, folder_name: &str) -> Option<String> {
+        find_file_by_extension(folder_name, &["main.c"], &[".c"])
+    }
+
+    fn find_source_files(&self, folder_name: &str) -> Vec<String> {
+        find_files_by_extension(folder_name, &[".c", ".h"])
+    }
+
+    fn check_commands(&self, task_path: &str, main_file: &str) -> Vec<String> {
+        let standards = ["c99", "c90", "c89", "c11", "c17"];
+        standards
+            .iter()
+            .map(|standard| format!("gcc -std={} -o /dev/null {}/{}", standard, task_path, escape(main_file)))
+            .collect()
+    }
+
+    fn build_command(&self, task_path: &str, main_file: &str, output: &str, flags: &str) -> String {
+        format!("gcc {} -o {} {}/{}", flags, output, task_path, escape(main_file))
+    }
+
+    fn run_command(&self, _task_path: &str, _main_file: &str, output: &str) -> String {
+        output.to_string()
+    }
+
+    fn driver_build_command(&self, task_path: &str, source_files: &[String], driver: &str, output: &str) -> Option<String> {
+        let mut commands = vec![];
+        let mut objects = vec![];
+        for (i, source) in source_files.iter().filter(|source| source.ends_with(".c")).enumerate() {
+            let object = format!("{}_{}.o", output, i);
+            commands.push(format!("gcc -c -g -Dmain=student_main -o {} {}/{}", object, task_path, escape(source)));
+            objects.push(object);
+        }
+        // the driver may include the student's headers
+        commands.push(format!("gcc -g -I{} -o {} {} {}", task_path, output, driver, objects.join(" ")));
+        Some(commands.join(" && "))
+    }
+
+    fn is_native(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,48 @@
+use crate::controllers::parser::escape;
+
+use super::{Language, find_file_by_extension, find_files_by_extension};
+
+pub struct Cpp;
+
+impl Language for Cpp {
+    fn name(&self) -> &'static str {
+        "cpp"
+    }
+
+    fn find_main_file(&self, folder_name: &str) -> Option<String> {
+        find_file_by_extension(folder_name, &["main.cpp", "main.cc"], &[".cpp", ".cc"])
+    }
+
+    fn find_source_files(&self, folder_name: &str) -> Vec<String> {
+        find_files_by_extension(folder_name, &[".cpp", ".cc", ".h", ".hpp"])
+    }
+
+    fn check_commands(&self, task_path: &str, main_file: &str) -> Vec<String> {
+        vec![format!("g++ -std=c++17 -o /dev/null {}/{}", task_path, escape(main_file))]
+    }
+
+    fn build_command(&self, task_path: &str, main_file: &str, output: &str, flags: &str) -> String {
+        format!("g++ -std=c++17 {} -o {} {}/{}", flags, output, task_path, escape(main_file))
+    }
+
+    fn run_command(&self, _task_path: &str, _main_file: &str, output: &str) -> String {
+        output.to_string()
+    }
+
+    fn driver_build_command(&self, task_path: &str, source_files: &[String], driver: &str, output: &str) -> Option<String> {
+        let mut commands = vec![];
+        let mut objects = vec![];
+        for (i, source) in source_files.iter().filter(|source| source.ends_with(".cpp") || source.ends_with(".cc")).enumerate() {
+            let object = format!("{}_{}.o", output, i);
+            commands.push(format!("g++ -std=c++17 -c -g -Dmain=student_main -o {} {}/{}", object, task_path, escape(source)));
+            objects.push(object);
+        }
+        // the driver may include the student's headers
+        commands.push(format!("g++ -std=c++17 -g -I{} -o {} {} {}", task_path, output, driver, objects.join(" ")));
+        Some(commands.join(" && "))
+    }
+
+    fn is_native(&self) -> bool {
+        true
+    }
+}
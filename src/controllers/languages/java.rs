@@ -0,0 +1,39 @@
+use super::{Language, find_file_by_extension, find_files_by_extension};
+
+pub struct Java;
+
+impl Language for Java {
+    fn name(&self) -> &'static str {
+        "java"
+    }
+
+    fn find_main_file(&self, folder_name: &str) -> Option<String> {
+        find_file_by_extension(folder_name, &["Main.java"], &[".java"])
+    }
+
+    fn find_source_files(&self, folder_name: &str) -> Vec<String> {
+        find_files_by_extension(folder_name, &[".java"])
+    }
+
+    fn check_commands(&self, task_path: &str, _main_file: &str) -> Vec<String> {
+        vec![format!("javac -d ./rp_workspace/bin/java_check {}/*.java", task_path)]
+    }
+
+    // output is a folder of class files
+    fn build_command(&self, task_path: &str, _main_file: &str, output: &str, _flags: &str) -> String {
+        format!("mkdir -p {} && javac -d {} {}/*.java", output, output, task_path)
+    }
+
+    // classes are expected in the default package
+    fn run_command(&self, _task_path: &str, main_file: &str, output: &str) -> String {
+        format!("java -cp {} {}", output, main_file.trim_end_matches(".java"))
+    }
+
+    fn driver_build_command(&self, _task_path: &str, _source_files: &[String], _driver: &str, _output: &str) -> Option<String> {
+        None
+    }
+
+    fn is_native(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,86 @@
+use std::{fs, path::Path};
+
+use crate::models::config::ResourceLimits;
+
+pub mod c;
+pub mod cpp;
+pub mod python;
+pub mod java;
+
+// Everything the judge needs to know about a language. Paths passed in are
+// already escaped for the shell, file names are not.
+pub trait Language {
+    fn name(&self) -> &'static str;
+
+    // entry point of the task, relative to the task folder
+    fn find_main_file(&self, folder_name: &str) -> Option<String>;
+
+    // all sources of the task folder, sorted by name
+    fn find_source_files(&self, folder_name: &str) -> Vec<String>;
+
+    // static checks run on every commit; the commit counts as compiling if
+    // any of the commands succeeds without output
+    fn check_commands(&self, task_path: &str, main_file: &str) -> Vec<String>;
+
+    // builds the task into output (binary or class folder)
+    fn build_command(&self, task_path: &str, main_file: &str, output: &str, flags: &str) -> String;
+
+    // runs a build made by build_command
+    fn run_command(&self, task_path: &str, main_file: &str, output: &str) -> String;
+
+    // builds the task sources with their main renamed to student_main and
+    // links them with an instructor test driver into output. None if the
+    // language has no such mode
+    fn driver_build_command(&self, task_path: &str, source_files: &[String], driver: &str, output: &str) -> Option<String>;
+
+    // native binaries can be run under sanitizers and valgrind
+    fn is_native(&self) -> bool;
+
+    // the JVM and the interpreters reserve far more virtual memory than they
+    // use, so their builds and runs are only limited in time
+    fn limits(&self, limits: &ResourceLimits) -> ResourceLimits {
+        match self.is_native() {
+            true => limits.clone(),
+            false => ResourceLimits::new(limits.timeout_secs, None),
+        }
+    }
+}
+
+pub fn get_language(name: &str) -> Option<Box<dyn Language>> {
+    match name.to_lowercase().as_str() {
+        "c" => Some(Box::new(c::C)),
+        "cpp" | "c++" => Some(Box::new(cpp::Cpp)),
+        "python" => Some(Box::new(python::Python)),
+        "java" => Some(Box::new(java::Java)),
+        _ => None,
+    }
+}
+
+// first of the preferred names present in the folder, otherwise the first
+// file with one of the extensions
+pub fn find_file_by_extension(folder_name: &str, preferred: &[&str], extensions: &[&str]) -> Option<String> {
+    let files = find_files_by_extension(folder_name, extensions);
+    for name in preferred.iter() {
+        if files.iter().any(|f| f == name) {
+            return Some(name.to_string());
+        }
+    }
+    files.into_iter().next()
+}
+
+pub fn find_files_by_extension(folder_name: &str, extensions: &[&str]) -> Vec<String> {
+    let path = Path::new(folder_name);
+    if !path.is_dir() {
+        return vec![];
+    }
+
+    let mut files: Vec<String> = fs::read_dir(path)
+        .unwrap()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| entry.file_name().into_string().unwrap())
+        .filter(|name| extensions.iter().any(|e| name.ends_with(e)))
+        .collect();
+    files.sort();
+    files
+}
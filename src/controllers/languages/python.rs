@@ -0,0 +1,41 @@
+use crate::controllers::parser::escape;
+
+use super::{Language, find_file_by_extension, find_files_by_extension};
+
+pub struct Python;
+
+impl Language for Python {
+    fn name(&self) -> &'static str {
+        "python"
+    }
+
+    fn find_main_file(&self, folder_name: &str) -> Option<String> {
+        find_file_by_extension(folder_name, &["main.py"], &[".py"])
+    }
+
+    fn find_source_files(&self, folder_name: &str) -> Vec<String> {
+        find_files_by_extension(folder_name, &[".py"])
+    }
+
+    // bytecode goes to the workspace instead of __pycache__ in the student repo
+    fn check_commands(&self, task_path: &str, main_file: &str) -> Vec<String> {
+        vec![format!("PYTHONPYCACHEPREFIX=./rp_workspace/bin/pycache python3 -m py_compile {}/{}", task_path, escape(main_file))]
+    }
+
+    // nothing to build, only the syntax is checked
+    fn build_command(&self, task_path: &str, main_file: &str, _output: &str, _flags: &str) -> String {
+        format!("PYTHONPYCACHEPREFIX=./rp_workspace/bin/pycache python3 -m py_compile {}/{}", task_path, escape(main_file))
+    }
+
+    fn run_command(&self, task_path: &str, main_file: &str, _output: &str) -> String {
+        format!("python3 {}/{}", task_path, escape(main_file))
+    }
+
+    fn driver_build_command(&self, _task_path: &str, _source_files: &[String], _driver: &str, _output: &str) -> Option<String> {
+        None
+    }
+
+    fn is_native(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::HashSet;
+
+use crate::models::{student_project::StudentProjectSubmission, config::AssignmentConfig, code_metrics::{CodeMetrics, CommitMetrics}};
+
+use super::{
+    validator::{check_dir_exists, tasks_to_check},
+    config_handler::get_task_language,
+    git_commit_handler::get_commits_from_submission,
+    c_lexer::{tokenize, code_tokens, find_functions, Token, TokenKind},
+    rule_checker::read_task_sources,
+    os_helper::run_command,
+    parser::escape,
+};
+
+
+pub fn compute_metrics(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[METRICS] Error reading repos directory!");
+        std::process::exit(1);
+    }
+
+    for submission in submissions.iter_mut() {
+        if !submission.cloned {
+            continue;
+        }
+
+        for task in tasks_to_check(submission).iter() {
+            // metrics work on tokenised C, which covers C++ well enough
+            let language = get_task_language(config, submission, task);
+            if language.name() != "c" && language.name() != "cpp" {
+                continue;
+            }
+            let folder = format!("./rp_workspace/repos/{}/{}", submission.student_folder, task);
+            let metrics = source_metrics(&read_task_sources(language.as_ref(), &folder));
+            save_metrics_to_submission(submission, task, metrics);
+
+            if !config.metrics_history {
+                continue;
+            }
+            let commits = match get_commits_from_submission(task, submission) {
+                Some(c) => c,
+                None => continue,
+            };
+            let mut history = vec![];
+            for commit in commits.iter().rev() {
+                if let Err(e) = run_command(&format!(
+                    "git -C ./rp_workspace/repos/{} checkout {}",
+                    escape(&submission.student_folder),
+                    commit
+                )) {
+                    println!("[METRICS] Error switching commits on repo({}): {:#?}", submission.student_folder, e);
+                    continue;
+                };
+                history.push(CommitMetrics {
+                    commit: commit.to_string(),
+                    metrics: source_metrics(&read_task_sources(language.as_ref(), &folder)),
+                });
+                if let Err(e) = run_command(&format!(
+                    "git -C ./rp_workspace/repos/{} checkout -",
+                    escape(&submission.student_folder)
+                )) {
+                    println!("[METRICS] Error switching commits on repo back to latest ({}): {:#?}", submission.student_folder, e);
+                };
+            }
+            save_metrics_history_to_submission(submission, task, history);
+        }
+    }
+}
+
+// sources are (file name, content) pairs
+fn source_metrics(sources: &[(String, String)]) -> CodeMetrics {
+    let mut metrics = CodeMetrics::default();
+    let mut complexities = vec![];
+
+    for (_, content) in sources.iter() {
+        metrics.physical_loc += content.lines().count();
+
+        let tokens = tokenize(content);
+        let mut comment_lines = HashSet::new();
+        for token in tokens.iter() {
+            match token.kind {
+                TokenKind::Comment => {
+                    // block comments span several lines
+                    let span = token.text.matches('\n').count();
+                    comment_lines.extend(token.line..=token.line + span);
+                },
+                TokenKind::Directive => metrics.logical_loc += 1,
+                _ => (),
+            }
+        }
+        metrics.comment_lines += comment_lines.len();
+
+        let code = code_tokens(&tokens);
+        metrics.logical_loc += statement_count(&code);
+
+        for function in find_functions(&code).into_iter() {
+            let body = &code[function.body_start..=function.body_end];
+            complexities.push(cyclomatic_complexity(body));
+            metrics.max_nesting = metrics.max_nesting.max(nesting_depth(body));
+
+            let lines = function.end_line - function.start_line + 1;
+            if lines > metrics.longest_function_lines {
+                metrics.longest_function_lines = lines;
+                metrics.longest_function = Some(function.name);
+            }
+        }
+    }
+
+    metrics.functions = complexities.len();
+    metrics.max_complexity = complexities.iter().copied().max().unwrap_or(0);
+    if !complexities.is_empty() {
+        metrics.average_complexity = complexities.iter().sum::<usize>() as f64 / complexities.len() as f64;
+    }
+    metrics
+}
+
+// semicolons outside of parentheses, so "for (;;)" is one statement
+fn statement_count(tokens: &[Token]) -> usize {
+    let mut depth = 0;
+    let mut statements = 0;
+    for token in tokens.iter() {
+        match token.text.as_str() {
+            "(" => depth += 1,
+            ")" => depth -= 1,
+            ";" if depth <= 0 => statements += 1,
+            _ => (),
+        }
+    }
+    statements
+}
+
+// McCabe: one plus the decision points of the body
+fn cyclomatic_complexity(body: &[Token]) -> usize {
+    1 + body
+        .iter()
+        .filter(|t| ["if", "for", "while", "case", "&&", "||", "?"].contains(&t.text.as_str()))
+        .count()
+}
+
+// blocks inside the body braces, a function without blocks has depth 0
+fn nesting_depth(body: &[Token]) -> usize {
+    let mut depth: usize = 0;
+    let mut max_depth = 0;
+    for token in body.iter() {
+        match token.text.as_str() {
+            "{" => {
+                depth += 1;
+                max_depth = max_depth.max(depth);
+            },
+            "}" => depth = depth.saturating_sub(1),
+            _ => (),
+        }
+    }
+    max_depth.saturating_sub(1)
+}
+
+fn save_metrics_to_submission(submission: &mut StudentProjectSubmission, task: &str, metrics: CodeMetrics) {
+    if let Some(task1) = submission.has_task1.clone() {
+        if task1 == task {
+            submission.metrics_task1 = Some(metrics);
+            return;
+        }
+    }
+
+    if let Some(task2) = submission.has_task2.clone() {
+        if task2 == task {
+            submission.metrics_task2 = Some(metrics);
+        }
+    }
+}
+
+fn save_metrics_history_to_submission(submission: &mut StudentProjectSubmission, task: &str, history: Vec<CommitMetrics>) {
+    if let Some(task1) = submission.has_task1.clone() {
+        if task1 == task {
+            submission.metrics_history_task1 = Some(history);
+            return;
+        }
+    }
+
+    if let Some(task2) = submission.has_task2.clone() {
+        if task2 == task {
+            submission.metrics_history_task2 = Some(history);
+        }
+    }
+}
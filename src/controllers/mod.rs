@@ -1,9 +1,31 @@
 pub mod os_helper;
 pub mod parser;
 pub mod validator;
+pub mod stats;
 pub mod workdir;
 pub mod git_clone_handler;
 pub mod exporter;
 pub mod git_commit_handler;
 pub mod git_compilation_handler;
-pub mod moss_handler;
\ No newline at end of file
+pub mod moss_handler;
+pub mod config_handler;
+pub mod sanitizer_handler;
+pub mod valgrind_handler;
+pub mod c_lexer;
+pub mod rule_checker;
+pub mod languages;
+pub mod unit_test_handler;
+pub mod test_handler;
+pub mod differential_handler;
+pub mod benchmark_handler;
+pub mod coverage_handler;
+pub mod metrics_handler;
+pub mod style_handler;
+pub mod feedback_handler;
+pub mod deadline_handler;
+pub mod authorship_handler;
+pub mod commit_quality_handler;
+pub mod timeline_handler;
+pub mod branch_handler;
+pub mod shared_history_handler;
+pub mod hygiene_handler;
\ No newline at end of file
@@ -1,4 +1,15 @@
-use std::{io::{Error, ErrorKind, self}, process::Command, fs, ffi::OsString};
+use std::{io::{Error, ErrorKind, self, Read}, process::{Command, Stdio}, fs, ffi::OsString, os::unix::process::CommandExt, thread, time::{Duration, Instant}};
+
+use crate::models::config::ResourceLimits;
+
+#[derive(Debug)]
+pub struct CommandOutput {
+    pub stdout: String,
+    pub stderr: String,
+    // None if the process was killed by a signal
+    pub status: Option<i32>,
+    pub timed_out: bool,
+}
 
 pub fn run_command(command: &str) -> Result<String, Error> {
     // println!("{}", command);
@@ -22,6 +33,98 @@ pub fn run_command(command: &str) -> Result<String, Error> {
     }
 }
 
+// same contract as run_command, but the command is killed once it exceeds the
+// limits. A timeout is reported with ErrorKind::TimedOut
+pub fn run_command_with_limits(command: &str, limits: &ResourceLimits) -> Result<String, Error> {
+    let output = run_limited_command(command, limits)?;
+    if output.timed_out {
+        return Err(Error::new(
+            ErrorKind::TimedOut, 
+            format!("[OS HELPER] Command timed out after {}s: {}", limits.timeout_secs, command))
+        );
+    }
+    match output.status {
+        Some(0) => Ok(output.stdout),
+        _ => Err(Error::new(
+            ErrorKind::Other, 
+            format!("[OS HELPER] Something went wrong with running command: {}", output.stderr))
+        ),
+    }
+}
+
+pub fn run_limited_command(command: &str, limits: &ResourceLimits) -> Result<CommandOutput, Error> {
+    let command = match limits.memory_mb {
+        Some(mb) => format!("ulimit -v {}; {}", mb * 1024, command),
+        None => command.to_string(),
+    };
+
+    // own process group, so everything the command spawns can be killed at once
+    let mut child = match Command::new("sh")
+        .arg("-c")
+        .arg(&command)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .process_group(0)
+        .spawn()
+    {
+        Ok(c) => c,
+        Err(e) => return Err(Error::new(
+            ErrorKind::Other, 
+            format!("[OS HELPER] Something went wrong with running command: {}", e))
+        ),
+    };
+
+    // drain the pipes while waiting, otherwise a chatty process blocks forever
+    let stdout_reader = read_in_background(child.stdout.take());
+    let stderr_reader = read_in_background(child.stderr.take());
+
+    let start = Instant::now();
+    let timeout = Duration::from_secs(limits.timeout_secs);
+    let mut timed_out = false;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if start.elapsed() >= timeout {
+            timed_out = true;
+            kill_process_group(child.id());
+            break child.wait()?;
+        }
+        thread::sleep(Duration::from_millis(20));
+    };
+    // clean up anything left running in the background
+    kill_process_group(child.id());
+
+    Ok(CommandOutput {
+        stdout: stdout_reader.join().unwrap_or_default(),
+        stderr: stderr_reader.join().unwrap_or_default(),
+        status: status.code(),
+        timed_out,
+    })
+}
+
+fn read_in_background<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buffer = vec![];
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        String::from_utf8_lossy(&buffer).to_string()
+    })
+}
+
+fn kill_process_group(pid: u32) {
+    let _ = Command::new("kill")
+        .arg("-KILL")
+        .arg("--")
+        .arg(format!("-{}", pid))
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
 pub fn create_workdir() -> Result<String, Error>  {
     println!("[OS HELPER] Creating working directory!");
     run_command("mkdir rp_workspace")
@@ -34,4 +137,4 @@ pub fn folder_names(path: &str) -> Result<Vec<String>, io::Error> {
         .filter(|entry| entry.file_type().unwrap().is_dir())
         .map(|entry| entry.file_name().into_string().unwrap())
         .collect::<Vec<String>>())
-}
\ No newline at end of file
+}
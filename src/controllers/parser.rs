@@ -27,6 +27,11 @@ pub fn parse_file_args(args: Vec<String>) -> Vec<FilePath> {
                 files.push(FilePath::Zip(arg.to_string()));
                 continue;
             }
+            // assignment configuration (limits, tasks, ...)
+            if path.extension().unwrap_or_default() == "ini" {
+                files.push(FilePath::Config(arg.to_string()));
+                continue;
+            }
         }
 
         println!("Error parsing path ({}): Unsupported file type!", arg);
@@ -34,9 +39,75 @@ pub fn parse_file_args(args: Vec<String>) -> Vec<FilePath> {
     files
 }
 
-pub fn escape(path: &String) -> String {
+// same repository, same string: "git@github.com:User/Repo.git",
+// "ssh://git@github.com:22/user/repo" and "http://www.github.com/user/repo/"
+// all become "github.com/user/repo"
+pub fn normalize_repo_url(url: &str) -> String {
+    let mut url = url.trim().to_lowercase();
+    let mut has_scheme = false;
+    for scheme in ["https://", "http://", "ssh://", "git://"] {
+        if let Some(rest) = url.strip_prefix(scheme) {
+            url = rest.to_string();
+            has_scheme = true;
+        }
+    }
+    // scp-like git@host:path, only without a scheme
+    if !has_scheme {
+        if let Some((_, rest)) = url.split_once('@') {
+            url = rest.replacen(':', "/", 1);
+        }
+    }
+    // scheme://user@host:port/path, the user and port are dropped
+    let (host, path) = url.split_once('/').unwrap_or((&url, ""));
+    let host = host.rsplit_once('@').map_or(host, |(_, h)| h);
+    let host = host.split_once(':').map_or(host, |(h, _)| h);
+    url = format!("{}/{}", host, path);
+    if let Some(rest) = url.strip_prefix("www.") {
+        url = rest.to_string();
+    }
+    let url = url.trim_end_matches('/');
+    url.strip_suffix(".git").unwrap_or(url).trim_end_matches('/').to_string()
+}
+
+pub fn escape(path: &str) -> String {
     path
         .replace(" ", "\\ ")
         .replace("&", "\\&")
         .replace("|", "\\|")
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_repo_url;
+
+    #[test]
+    fn normalizes_equivalent_repo_urls() {
+        let urls = [
+            "https://github.com/user/repo",
+            "https://github.com/User/Repo",
+            "https://github.com/user/repo.git",
+            "https://github.com/user/repo/",
+            "https://github.com/user/repo.git/",
+            "http://github.com/user/repo",
+            "https://www.github.com/user/repo",
+            "http://www.github.com/user/repo/",
+            "https://user@github.com/user/repo.git",
+            "git@github.com:user/repo.git",
+            "git@github.com:User/Repo",
+            "ssh://git@github.com/user/repo.git",
+            "ssh://git@github.com:22/user/repo.git",
+            "git://github.com/user/repo.git",
+            "  github.com/user/repo  ",
+        ];
+        for url in urls {
+            assert_eq!(normalize_repo_url(url), "github.com/user/repo", "{}", url);
+        }
+    }
+
+    #[test]
+    fn keeps_different_repos_apart() {
+        assert_ne!(normalize_repo_url("https://github.com/user/repo"), normalize_repo_url("https://github.com/user/repo2"));
+        assert_ne!(normalize_repo_url("https://github.com/user/repo"), normalize_repo_url("https://gitlab.com/user/repo"));
+        assert_ne!(normalize_repo_url("git@github.com:user/repo"), normalize_repo_url("git@github.com:other/repo"));
+    }
+}
@@ -0,0 +1,235 @@
+use std::{collections::HashSet, fs};
+
+use regex::Regex;
+
+use crate::models::{student_project::StudentProjectSubmission, config::{AssignmentConfig, TaskRules}, rule_violation::RuleViolation};
+
+use super::{
+    validator::{check_dir_exists, tasks_to_check},
+    config_handler::{get_task_config, get_task_language},
+    c_lexer::{tokenize, code_tokens, find_functions, join_tokens, TokenKind, FunctionDefinition},
+    languages::Language,
+};
+
+
+pub fn check_rules(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[RULES] Error reading repos directory!");
+        std::process::exit(1);
+    }
+
+    for submission in submissions.iter_mut() {
+        if !submission.cloned {
+            continue;
+        }
+
+        for task in tasks_to_check(submission).iter() {
+            let task_config = match get_task_config(config, submission, task) {
+                Some(c) => c,
+                None => continue,
+            };
+            if !has_rules(&task_config.rules) {
+                continue;
+            }
+            // rules work on tokenised C, which covers C++ well enough
+            let language = get_task_language(config, submission, task);
+            if language.name() != "c" && language.name() != "cpp" {
+                continue;
+            }
+
+            let folder = format!("./rp_workspace/repos/{}/{}", submission.student_folder, task);
+            let sources = read_task_sources(language.as_ref(), &folder);
+            let violations = check_task_rules(&task_config.rules, &sources);
+            save_rule_violations_to_submission(submission, task, violations);
+        }
+    }
+}
+
+// (file name, content) of the sources of a task folder
+pub fn read_task_sources(language: &dyn Language, folder: &str) -> Vec<(String, String)> {
+    let mut sources = vec![];
+    for file in language.find_source_files(folder).into_iter() {
+        match fs::read_to_string(format!("{}/{}", folder, file)) {
+            Ok(content) => sources.push((file, content)),
+            Err(e) => println!("[RULES] Error reading source ({}/{}): {:#?}", folder, file, e),
+        }
+    }
+    sources
+}
+
+fn has_rules(rules: &TaskRules) -> bool {
+    !rules.banned_headers.is_empty()
+        || !rules.banned_functions.is_empty()
+        || !rules.required_functions.is_empty()
+        || !rules.required_keywords.is_empty()
+        || rules.require_recursion
+        || rules.max_lines.is_some()
+}
+
+// sources are (file name, content) pairs
+fn check_task_rules(rules: &TaskRules, sources: &[(String, String)]) -> Vec<RuleViolation> {
+    let include = Regex::new(r#"^#\s*include\s*[<"]([^>"]+)[>"]"#).unwrap();
+
+    let mut violations = vec![];
+    let mut used_words: HashSet<String> = HashSet::new();
+    // (file, definition)
+    let mut functions: Vec<(String, FunctionDefinition)> = vec![];
+    let mut has_recursion = false;
+
+    for (file, content) in sources.iter() {
+        if let Some(max_lines) = rules.max_lines {
+            let lines = content.lines().count();
+            if lines > max_lines {
+                violations.push(RuleViolation {
+                    rule: "max_lines".to_string(),
+                    file: Some(file.to_string()),
+                    line: None,
+                    message: format!("{} lines, at most {} allowed", lines, max_lines),
+                });
+            }
+        }
+
+        let tokens = tokenize(content);
+        for token in tokens.iter() {
+            match token.kind {
+                TokenKind::Directive => {
+                    let header = match include.captures(&token.text) {
+                        Some(capture) => capture[1].trim().to_string(),
+                        None => continue,
+                    };
+                    if rules.banned_headers.contains(&header) {
+                        violations.push(RuleViolation {
+                            rule: "banned_headers".to_string(),
+                            file: Some(file.to_string()),
+                            line: Some(token.line),
+                            message: format!("includes {}", header),
+                        });
+                    }
+                },
+                TokenKind::Identifier | TokenKind::Keyword => {
+                    used_words.insert(token.text.clone());
+                },
+                _ => (),
+            }
+        }
+
+        let code = code_tokens(&tokens);
+        for (i, token) in code.iter().enumerate() {
+            let is_call = token.kind == TokenKind::Identifier
+                && code.get(i + 1).map(|t| t.text == "(").unwrap_or(false);
+            if is_call && rules.banned_functions.contains(&token.text) {
+                violations.push(RuleViolation {
+                    rule: "banned_functions".to_string(),
+                    file: Some(file.to_string()),
+                    line: Some(token.line),
+                    message: format!("uses {}", token.text),
+                });
+            }
+        }
+
+        for function in find_functions(&code).into_iter() {
+            let body = &code[function.body_start..function.body_end];
+            let calls_itself = body.windows(2).any(|w| w[0].text == function.name && w[1].text == "(");
+            has_recursion = has_recursion || calls_itself;
+            functions.push((file.to_string(), function));
+        }
+    }
+
+    for required in rules.required_functions.iter() {
+        if let Some(violation) = check_required_function(required, &functions) {
+            violations.push(violation);
+        }
+    }
+
+    for keyword in rules.required_keywords.iter() {
+        if !used_words.contains(keyword) {
+            violations.push(RuleViolation {
+                rule: "required_keywords".to_string(),
+                file: None,
+                line: None,
+                message: format!("{} is never used", keyword),
+            });
+        }
+    }
+
+    if rules.require_recursion && !has_recursion {
+        violations.push(RuleViolation {
+            rule: "require_recursion".to_string(),
+            file: None,
+            line: None,
+            message: "no function calls itself".to_string(),
+        });
+    }
+    violations
+}
+
+// required is either a name ("fib") or a signature ("int fib(int)")
+fn check_required_function(required: &str, functions: &[(String, FunctionDefinition)]) -> Option<RuleViolation> {
+    let wanted = if required.contains('(') {
+        // parse the signature as a definition with an empty body
+        find_functions(&code_tokens(&tokenize(&format!("{} {{}}", required)))).into_iter().next()
+    } else {
+        None
+    };
+    let name = match &wanted {
+        Some(w) => w.name.clone(),
+        None => required.to_string(),
+    };
+
+    let candidates: Vec<&(String, FunctionDefinition)> = functions.iter().filter(|(_, f)| f.name == name).collect();
+    if candidates.is_empty() {
+        return Some(RuleViolation {
+            rule: "required_functions".to_string(),
+            file: None,
+            line: None,
+            message: format!("function {} is missing", name),
+        });
+    }
+
+    let wanted = wanted?;
+    let matches = candidates.iter().any(|(_, f)| {
+        strip_specifiers(&f.return_type) == strip_specifiers(&wanted.return_type) && f.params == wanted.params
+    });
+    if matches {
+        return None;
+    }
+    let (file, found) = candidates[0];
+    Some(RuleViolation {
+        rule: "required_functions".to_string(),
+        file: Some(file.to_string()),
+        line: Some(found.start_line),
+        message: format!(
+            "{} has signature {} {}({}), expected {}",
+            name,
+            found.return_type,
+            name,
+            found.params.join(", "),
+            required
+        ),
+    })
+}
+
+// storage class and function specifiers do not change the signature
+fn strip_specifiers(return_type: &str) -> String {
+    let tokens = tokenize(return_type);
+    let kept: Vec<_> = tokens
+        .into_iter()
+        .filter(|t| !["static", "inline", "extern", "_Noreturn"].contains(&t.text.as_str()))
+        .collect();
+    join_tokens(&kept)
+}
+
+fn save_rule_violations_to_submission(submission: &mut StudentProjectSubmission, task: &str, violations: Vec<RuleViolation>) {
+    if let Some(task1) = submission.has_task1.clone() {
+        if task1 == task {
+            submission.rule_violations_task1 = Some(violations);
+            return;
+        }
+    }
+
+    if let Some(task2) = submission.has_task2.clone() {
+        if task2 == task {
+            submission.rule_violations_task2 = Some(violations);
+        }
+    }
+}
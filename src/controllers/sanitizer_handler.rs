@@ -0,0 +1,179 @@
+use std::io::ErrorKind;
+
+use regex::Regex;
+
+use crate::models::{student_project::StudentProjectSubmission, config::{AssignmentConfig, ResourceLimits}, sanitizer_finding::SanitizerFinding};
+
+use super::{
+    validator::{check_dir_exists, tasks_to_check},
+    config_handler::{get_task_config, get_task_inputs, get_task_language},
+    git_compilation_handler::{build_task, get_submission_main_file},
+    os_helper::run_limited_command,
+    parser::escape,
+};
+
+
+pub fn run_sanitizers(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[SANITIZER] Error reading repos directory!");
+        std::process::exit(1);
+    }
+
+    // ASan reserves terabytes of virtual memory up front, so a ulimit on
+    // virtual memory would kill every run
+    let run_limits = ResourceLimits::new(config.run_limits.timeout_secs, None);
+
+    for submission in submissions.iter_mut() {
+        if !submission.cloned {
+            continue;
+        }
+
+        for task in tasks_to_check(submission).iter() {
+            let task_main_file = match get_submission_main_file(submission, task) {
+                Some(main) => main,
+                None => continue,
+            };
+            let task_config = match get_task_config(config, submission, task) {
+                Some(c) => c,
+                None => continue,
+            };
+            // only native binaries can be instrumented
+            let language = get_task_language(config, submission, task);
+            if !language.is_native() {
+                continue;
+            }
+
+            let binary = match build_task(
+                language.as_ref(),
+                &submission.student_folder,
+                task,
+                &task_main_file,
+                "-g -fsanitize=address,undefined -fno-omit-frame-pointer",
+                "asan",
+                &config.compile_limits
+            ) {
+                Ok(b) => b,
+                Err(e) => {
+                    println!("[SANITIZER] Error building task ({}) of ({}): {:#?}", task, submission.student_folder, e);
+                    if e.kind() == ErrorKind::TimedOut {
+                        submission.timeouts.push(format!("sanitizer build {}", task));
+                    }
+                    continue;
+                },
+            };
+
+            let mut findings: Vec<SanitizerFinding> = vec![];
+            for input in get_task_inputs(task_config).iter() {
+                // leaks are left to the valgrind stage
+                let mut command = format!("ASAN_OPTIONS=detect_leaks=0 UBSAN_OPTIONS=print_stacktrace=1 {}", binary);
+                if let Some(input) = input {
+                    command = format!("{} < {}", command, escape(input));
+                }
+
+                let output = match run_limited_command(&command, &run_limits) {
+                    Ok(o) => o,
+                    Err(e) => {
+                        println!("[SANITIZER] Error running task ({}) of ({}): {:#?}", task, submission.student_folder, e);
+                        continue;
+                    },
+                };
+                if output.timed_out {
+                    submission.timeouts.push(format!("sanitizer run {}", task));
+                }
+
+                for finding in parse_sanitizer_output(&output.stderr, input).into_iter() {
+                    let duplicate = findings.iter().any(|f| {
+                        f.kind == finding.kind && f.file == finding.file && f.line == finding.line
+                    });
+                    if !duplicate {
+                        findings.push(finding);
+                    }
+                }
+            }
+            save_sanitizer_findings_to_submission(submission, task, findings);
+        }
+    }
+}
+
+fn parse_sanitizer_output(stderr: &str, input: &Option<String>) -> Vec<SanitizerFinding> {
+    // ==123==ERROR: AddressSanitizer: heap-use-after-free on address ...
+    let asan_header = Regex::new(r"ERROR: AddressSanitizer: ([\w-]+)").unwrap();
+    // main.c:5:10: runtime error: signed integer overflow: ...
+    let ubsan_header = Regex::new(r"^(.+?):(\d+):(?:\d+:)? runtime error: ([^:]+)").unwrap();
+    //     #0 0x55d0c1 in main /path/to/main.c:12
+    let frame = Regex::new(r"^\s*#\d+ 0x[0-9a-f]+ in (\S+) (.+?):(\d+)(?::\d+)?$").unwrap();
+
+    let mut findings = vec![];
+    // the stack trace following a header belongs to that finding
+    let mut pending: Option<SanitizerFinding> = None;
+    for line in stderr.lines() {
+        if let Some(capture) = asan_header.captures(line) {
+            if let Some(finding) = pending.take() {
+                findings.push(finding);
+            }
+            pending = Some(SanitizerFinding {
+                kind: capture[1].to_string(),
+                input: input.clone(),
+                function: None,
+                file: None,
+                line: None,
+            });
+            continue;
+        }
+
+        if let Some(capture) = ubsan_header.captures(line) {
+            if let Some(finding) = pending.take() {
+                findings.push(finding);
+            }
+            // the location is known, the stack trace only adds the function
+            pending = Some(SanitizerFinding {
+                kind: capture[3].trim().to_string(),
+                input: input.clone(),
+                function: None,
+                file: Some(student_file_name(&capture[1])),
+                line: capture[2].parse().ok(),
+            });
+            continue;
+        }
+
+        if let Some(finding) = pending.as_mut() {
+            if finding.function.is_some() {
+                continue;
+            }
+            if let Some(capture) = frame.captures(line) {
+                // skip frames in libc and the sanitizer runtime
+                if !capture[2].contains("rp_workspace/repos/") {
+                    continue;
+                }
+                finding.function = Some(capture[1].to_string());
+                if finding.file.is_none() {
+                    finding.file = Some(student_file_name(&capture[2]));
+                    finding.line = capture[3].parse().ok();
+                }
+            }
+        }
+    }
+    if let Some(finding) = pending.take() {
+        findings.push(finding);
+    }
+    findings
+}
+
+fn student_file_name(path: &str) -> String {
+    path.rsplit('/').next().unwrap_or(path).to_string()
+}
+
+fn save_sanitizer_findings_to_submission(submission: &mut StudentProjectSubmission, task: &str, findings: Vec<SanitizerFinding>) {
+    if let Some(task1) = submission.has_task1.clone() {
+        if task1 == task {
+            submission.sanitizer_findings_task1 = Some(findings);
+            return;
+        }
+    }
+
+    if let Some(task2) = submission.has_task2.clone() {
+        if task2 == task {
+            submission.sanitizer_findings_task2 = Some(findings);
+        }
+    }
+}
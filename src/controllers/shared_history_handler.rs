@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use chrono::DateTime;
+
+use crate::models::{student_project::StudentProjectSubmission, config::AssignmentConfig, shared_history::SharedHistory};
+
+use super::{
+    validator::check_dir_exists,
+    os_helper::run_command,
+    parser::escape,
+};
+
+
+struct IndexedCommit {
+    // committer date as unix time
+    time: i64,
+    author: String,
+    is_root: bool,
+    // indices of the submissions that have the commit
+    submissions: Vec<usize>,
+}
+
+// indexes the commits of every branch of every repo and groups the
+// submissions that share any of them
+pub fn detect_shared_history(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[SHARED HISTORY] Error reading repos directory!");
+        std::process::exit(1);
+    }
+
+    let mut index: HashMap<String, IndexedCommit> = HashMap::new();
+    for (i, submission) in submissions.iter().enumerate() {
+        // jordan's submissions share one repo by design
+        if !submission.cloned || submission.jordan {
+            continue;
+        }
+        let output = match run_command(&format!(
+            "git -C ./rp_workspace/repos/{} --no-pager log --all --format=\"%H%x1f%ct%x1f%an <%ae>%x1f%P\"",
+            escape(&submission.student_folder)
+        )) {
+            Ok(o) => o,
+            Err(e) => {
+                println!("[SHARED HISTORY] Error reading commits of repo ({}): {:#?}", submission.student_folder, e);
+                continue;
+            },
+        };
+
+        for line in output.lines() {
+            let fields: Vec<&str> = line.split('\x1f').collect();
+            if fields.len() != 4 || is_ignored(fields[0], &config.ignore_shared_commits) {
+                continue;
+            }
+            let commit = index.entry(fields[0].to_string()).or_insert_with(|| IndexedCommit {
+                time: fields[1].parse().unwrap_or(0),
+                author: fields[2].to_string(),
+                is_root: fields[3].trim().is_empty(),
+                submissions: vec![],
+            });
+            if !commit.submissions.contains(&i) {
+                commit.submissions.push(i);
+            }
+        }
+    }
+
+    // union-find over the submissions that share a commit
+    let mut parent: Vec<usize> = (0..submissions.len()).collect();
+    for commit in index.values().filter(|c| c.submissions.len() > 1) {
+        let first = find(&mut parent, commit.submissions[0]);
+        for other in commit.submissions[1..].iter() {
+            let other = find(&mut parent, *other);
+            parent[other] = first;
+        }
+    }
+
+    let mut groups: Vec<Vec<usize>> = vec![];
+    let mut group_of: HashMap<usize, usize> = HashMap::new();
+    for i in 0..submissions.len() {
+        let root = find(&mut parent, i);
+        let group = *group_of.entry(root).or_insert_with(|| {
+            groups.push(vec![]);
+            groups.len() - 1
+        });
+        groups[group].push(i);
+    }
+
+    for (number, members) in groups.iter().filter(|g| g.len() > 1).enumerate() {
+        let shared: Vec<(&String, &IndexedCommit)> = index
+            .iter()
+            .filter(|(_, c)| c.submissions.len() > 1 && members.contains(&c.submissions[0]))
+            .collect();
+        // a root is older than its children committed in the same second
+        let (first_hash, first) = match shared.iter().min_by_key(|(hash, c)| (c.time, !c.is_root, *hash)) {
+            Some(f) => *f,
+            None => continue,
+        };
+
+        let history = SharedHistory {
+            group: number + 1,
+            shared_commits: shared.len(),
+            shared_roots: shared.iter().filter(|(_, c)| c.is_root).count(),
+            first_commit: first_hash.chars().take(7).collect(),
+            first_author: first.author.clone(),
+            first_date: DateTime::from_timestamp(first.time, 0).map(|d| d.to_string()).unwrap_or_default(),
+        };
+        for i in members.iter() {
+            submissions[*i].shared_history = Some(history.clone());
+        }
+    }
+}
+
+fn find(parent: &mut [usize], i: usize) -> usize {
+    let mut root = i;
+    while parent[root] != root {
+        root = parent[root];
+    }
+    parent[i] = root;
+    root
+}
+
+// ignored commits are given as hash prefixes, e.g. the commits of a template repo
+fn is_ignored(hash: &str, ignored: &[String]) -> bool {
+    ignored.iter().any(|prefix| hash.starts_with(prefix.as_str()))
+}
@@ -0,0 +1,257 @@
+use std::{collections::HashSet, fs, io::{Error, ErrorKind}};
+
+use regex::Regex;
+
+use crate::models::{student_project::StudentProjectSubmission, config::{AssignmentConfig, ResourceLimits, StyleConfig}, style_report::{StyleReport, StyleIssue}};
+
+use super::{
+    validator::{check_dir_exists, tasks_to_check},
+    config_handler::get_task_language,
+    git_compilation_handler::workspace_name,
+    os_helper::run_command_with_limits,
+    parser::escape,
+};
+
+
+pub fn check_style(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[STYLE] Error reading repos directory!");
+        std::process::exit(1);
+    }
+
+    for submission in submissions.iter_mut() {
+        if !submission.cloned {
+            continue;
+        }
+
+        for task in tasks_to_check(submission).iter() {
+            let language = get_task_language(config, submission, task);
+            let folder = format!("./rp_workspace/repos/{}/{}", submission.student_folder, task);
+            // the suggested formatting is written here to diff against
+            let suggestion_file = format!(
+                "./rp_workspace/run/{}",
+                workspace_name(&submission.student_folder, task, "style")
+            );
+
+            let mut report = StyleReport::default();
+            for file in language.find_source_files(&folder).iter() {
+                let path = format!("{}/{}", folder, file);
+                let label = format!("{}/{}", task, file);
+                let content = match fs::read_to_string(&path) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        println!("[STYLE] Error reading source ({}): {:#?}", path, e);
+                        continue;
+                    },
+                };
+
+                let (issues, suggestion) = match &config.style_config.clang_format {
+                    Some(style) => match run_command_with_limits(&format!("clang-format --style={} {}", escape(style), escape(&path)), &config.compile_limits) {
+                        Ok(formatted) => (Vec::new(), formatted),
+                        Err(e) => {
+                            println!("[STYLE] Error running clang-format on ({}): {:#?}", path, e);
+                            if e.kind() == ErrorKind::TimedOut {
+                                submission.timeouts.push(format!("clang-format {}", label));
+                            }
+                            continue;
+                        },
+                    },
+                    // python blocks have no braces
+                    None => builtin_check(&label, &content, &config.style_config, language.name() != "python"),
+                };
+
+                let diff = match unified_diff(&path, &label, &suggestion, &suggestion_file, &config.compile_limits) {
+                    Ok(d) => d,
+                    Err(e) => {
+                        println!("[STYLE] Error comparing ({}) with the suggested formatting: {:#?}", path, e);
+                        continue;
+                    },
+                };
+                // clang-format only tells us which lines it would change
+                let issues = match config.style_config.clang_format {
+                    Some(_) => changed_lines(&diff)
+                        .into_iter()
+                        .map(|line| StyleIssue {
+                            file: label.clone(),
+                            line,
+                            message: "differs from the clang-format style".to_string(),
+                        })
+                        .collect(),
+                    None => issues,
+                };
+
+                report.deviating_lines += issues.iter().map(|i| i.line).collect::<HashSet<usize>>().len();
+                report.issues.extend(issues);
+                report.diff.push_str(&diff);
+            }
+            save_style_report_to_submission(submission, task, report);
+        }
+    }
+}
+
+// issues of the built-in checks and the content with the fixable ones fixed:
+// trailing whitespace and the indentation character
+fn builtin_check(label: &str, content: &str, config: &StyleConfig, check_braces: bool) -> (Vec<StyleIssue>, String) {
+    let lines: Vec<&str> = content.lines().collect();
+
+    // the style the file mostly uses is the expected one
+    let tab_lines = lines.iter().filter(|l| l.starts_with('\t')).count();
+    let space_lines = lines.iter().filter(|l| l.starts_with(' ') && !l.trim().is_empty()).count();
+    let use_tabs = tab_lines > space_lines;
+    let indent_unit = indent_unit(&lines);
+    let own_line_braces = lines.iter().filter(|l| l.trim() == "{").count();
+    let end_of_line_braces = lines.iter().filter(|l| opens_block_at_end(l)).count();
+    let braces_on_own_line = own_line_braces > end_of_line_braces;
+
+    let mut issues = vec![];
+    let mut fixed = vec![];
+    let mut previous = "";
+    for (i, line) in lines.iter().enumerate() {
+        let mut messages = vec![];
+        let trimmed = line.trim();
+        let indent = &line[..line.len() - line.trim_start().len()];
+
+        if line.len() != line.trim_end().len() {
+            messages.push("trailing whitespace".to_string());
+        }
+        let length = line.chars().count();
+        if length > config.max_line_length {
+            messages.push(format!("line is {} characters long, at most {}", length, config.max_line_length));
+        }
+
+        if indent.contains(' ') && indent.contains('\t') {
+            messages.push("indentation mixes tabs and spaces".to_string());
+        } else if use_tabs && indent.contains(' ') {
+            messages.push("indented with spaces, the file uses tabs".to_string());
+        } else if !use_tabs && indent.contains('\t') {
+            messages.push("indented with tabs, the file uses spaces".to_string());
+        } else if !use_tabs && indent.len() % indent_unit != 0 && !trimmed.starts_with('*') && ends_statement(previous) {
+            // continuation lines may be aligned freely
+            messages.push(format!("indented by {} spaces, not a multiple of {}", indent.len(), indent_unit));
+        }
+
+        if check_braces {
+            if braces_on_own_line && opens_block_at_end(line) {
+                messages.push("opening brace at the end of the line, the file puts them on their own line".to_string());
+            } else if !braces_on_own_line && trimmed == "{" {
+                messages.push("opening brace on its own line, the file puts them at the end of the line".to_string());
+            }
+        }
+
+        for message in messages.into_iter() {
+            issues.push(StyleIssue { file: label.to_string(), line: i + 1, message });
+        }
+        fixed.push(format!("{}{}", reindent(indent, use_tabs, indent_unit), line.trim()));
+        if !trimmed.is_empty() {
+            previous = trimmed;
+        }
+    }
+
+    let mut suggestion = fixed.join("\n");
+    if content.ends_with('\n') {
+        suggestion.push('\n');
+    }
+    (issues, suggestion)
+}
+
+// most common increase of the space indentation between consecutive lines
+fn indent_unit(lines: &[&str]) -> usize {
+    let widths: Vec<usize> = lines
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start_matches(' ').len())
+        .collect();
+    let mut counts = [0; 9];
+    for pair in widths.windows(2) {
+        if pair[1] > pair[0] && pair[1] - pair[0] <= 8 {
+            counts[pair[1] - pair[0]] += 1;
+        }
+    }
+    match (2..=8).max_by_key(|step| (counts[*step], *step == 4)) {
+        Some(step) if counts[step] > 0 => step,
+        _ => 4,
+    }
+}
+
+// "if (x) {" but not "int a[] = {" or a brace on its own
+fn opens_block_at_end(line: &str) -> bool {
+    let trimmed = line.trim_end();
+    trimmed.ends_with('{')
+        && trimmed.trim() != "{"
+        && !trimmed[..trimmed.len() - 1].trim_end().ends_with('=')
+}
+
+// the next line starts a new statement rather than continuing this one
+fn ends_statement(line: &str) -> bool {
+    line.is_empty()
+        || line.ends_with(';')
+        || line.ends_with('{')
+        || line.ends_with('}')
+        || line.ends_with(':')
+        || line.starts_with('#')
+        || line.starts_with("//")
+        || line.ends_with("*/")
+}
+
+// the same indentation width written with the expected character
+fn reindent(indent: &str, use_tabs: bool, indent_unit: usize) -> String {
+    let width: usize = indent.chars().map(|c| if c == '\t' { indent_unit } else { 1 }).sum();
+    if use_tabs {
+        format!("{}{}", "\t".repeat(width / indent_unit), " ".repeat(width % indent_unit))
+    } else {
+        " ".repeat(width)
+    }
+}
+
+fn unified_diff(path: &str, label: &str, suggestion: &str, suggestion_file: &str, limits: &ResourceLimits) -> Result<String, Error> {
+    fs::write(suggestion_file, suggestion)?;
+    // diff exits with 1 if the files differ
+    run_command_with_limits(&format!(
+        "diff -u --label {} --label {} {} {} || test $? -eq 1",
+        escape(label),
+        escape(label),
+        escape(path),
+        escape(suggestion_file)
+    ), limits)
+}
+
+// lines of the original file a unified diff removes or changes
+fn changed_lines(diff: &str) -> Vec<usize> {
+    let hunk = Regex::new(r"^@@ -(\d+)").unwrap();
+    let mut lines = vec![];
+    let mut line = 0;
+    // the "---" and "+++" file headers come before the first hunk
+    let mut in_hunk = false;
+    for diff_line in diff.lines() {
+        if let Some(capture) = hunk.captures(diff_line) {
+            line = capture[1].parse().unwrap_or(0);
+            in_hunk = true;
+            continue;
+        }
+        if !in_hunk {
+            continue;
+        }
+        if diff_line.starts_with('-') {
+            lines.push(line);
+            line += 1;
+        } else if diff_line.starts_with(' ') {
+            line += 1;
+        }
+    }
+    lines
+}
+
+fn save_style_report_to_submission(submission: &mut StudentProjectSubmission, task: &str, report: StyleReport) {
+    if let Some(task1) = submission.has_task1.clone() {
+        if task1 == task {
+            submission.style_task1 = Some(report);
+            return;
+        }
+    }
+
+    if let Some(task2) = submission.has_task2.clone() {
+        if task2 == task {
+            submission.style_task2 = Some(report);
+        }
+    }
+}
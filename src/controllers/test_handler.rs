@@ -0,0 +1,470 @@
+use std::{collections::HashMap, env, fs, io::{Error, ErrorKind}, path::Path};
+
+use regex::Regex;
+
+use crate::models::{student_project::StudentProjectSubmission, config::{AssignmentConfig, ResourceLimits}, test_case::{TestCase, TestResult, CompareMode}, test_history::{TestHistory, CommitTestRun, TestRegression}};
+
+use super::{
+    validator::{check_dir_exists, tasks_to_check},
+    config_handler::{get_task_config, get_task_language, get_task_tests},
+    git_compilation_handler::{build_task, get_submission_main_file, workspace_name},
+    git_commit_handler::get_commits_from_submission,
+    os_helper::{run_command, run_command_with_limits, run_limited_command, CommandOutput},
+    parser::escape,
+};
+
+
+pub fn run_tests(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[TESTS] Error reading repos directory!");
+        std::process::exit(1);
+    }
+    // built when loading the config
+    let checkers = &config.instructor_programs;
+
+    for submission in submissions.iter_mut() {
+        if !submission.cloned {
+            continue;
+        }
+
+        for task in tasks_to_check(submission).iter() {
+            let tests = match get_task_config(config, submission, task) {
+                Some(c) => get_task_tests(c),
+                None => continue,
+            };
+            if tests.is_empty() {
+                continue;
+            }
+            let task_main_file = match get_submission_main_file(submission, task) {
+                Some(main) => main,
+                None => continue,
+            };
+            let language = get_task_language(config, submission, task);
+
+            let run_command = match build_task(
+                language.as_ref(),
+                &submission.student_folder,
+                task,
+                &task_main_file,
+                "",
+                "tests",
+                &language.limits(&config.compile_limits)
+            ) {
+                Ok(c) => c,
+                Err(e) => {
+                    println!("[TESTS] Error building task ({}) of ({}): {:#?}", task, submission.student_folder, e);
+                    if e.kind() == ErrorKind::TimedOut {
+                        submission.timeouts.push(format!("test build {}", task));
+                    }
+                    // a task that does not build fails every case
+                    let results = tests
+                        .iter()
+                        .map(|test| failed_result(test, "does not build".to_string()))
+                        .collect();
+                    save_test_results_to_submission(submission, task, results);
+                    continue;
+                },
+            };
+
+            let results = run_cases(&run_command, &tests, checkers, &submission.student_folder, task, &language.limits(&config.run_limits));
+            for result in results.iter().filter(|r| r.timed_out) {
+                submission.timeouts.push(format!("test {} {}", task, result.name));
+            }
+            save_test_results_to_submission(submission, task, results);
+        }
+    }
+}
+
+// runs the test cases on every commit of a task, oldest first, like compile_commits
+pub fn run_test_history(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[TESTS] Error reading repos directory!");
+        std::process::exit(1);
+    }
+    // built when loading the config
+    let checkers = &config.instructor_programs;
+
+    for submission in submissions.iter_mut() {
+        if !submission.cloned {
+            continue;
+        }
+
+        for task in tasks_to_check(submission).iter() {
+            let tests = match get_task_config(config, submission, task) {
+                Some(c) => get_task_tests(c),
+                None => continue,
+            };
+            if tests.is_empty() {
+                continue;
+            }
+            let task_main_file = match get_submission_main_file(submission, task) {
+                Some(main) => main,
+                None => continue,
+            };
+            let commits = match get_commits_from_submission(task, submission) {
+                Some(c) => c,
+                None => continue,
+            };
+            let language = get_task_language(config, submission, task);
+            let student_folder = submission.student_folder.clone();
+
+            let mut runs = vec![];
+            for commit in commits.iter().rev() {
+                if let Err(e) = run_command(&format!(
+                    "git -C ./rp_workspace/repos/{} checkout {}",
+                    escape(&student_folder),
+                    commit
+                )) {
+                    println!("[TESTS] Error switching commits on repo({}): {:#?}", student_folder, e);
+                    continue;
+                };
+
+                let run = match build_task(
+                    language.as_ref(),
+                    &student_folder,
+                    task,
+                    &task_main_file,
+                    "",
+                    "history",
+                    &language.limits(&config.compile_limits)
+                ) {
+                    Ok(run_command) => {
+                        let results = run_cases(&run_command, &tests, checkers, &student_folder, task, &language.limits(&config.run_limits));
+                        CommitTestRun {
+                            commit: commit.to_string(),
+                            built: true,
+                            passed: results.into_iter().filter(|r| r.passed).map(|r| r.name).collect(),
+                        }
+                    },
+                    Err(_) => CommitTestRun { commit: commit.to_string(), built: false, passed: vec![] },
+                };
+                runs.push(run);
+
+                if let Err(e) = run_command(&format!(
+                    "git -C ./rp_workspace/repos/{} checkout -",
+                    escape(&student_folder)
+                )) {
+                    println!("[TESTS] Error switching commits on repo back to latest ({}): {:#?}", student_folder, e);
+                };
+            }
+            if !runs.is_empty() {
+                save_test_history_to_submission(submission, task, summarize_history(&tests, runs));
+            }
+        }
+    }
+}
+
+pub fn run_cases(
+    run_command: &str,
+    tests: &[TestCase],
+    checkers: &HashMap<String, String>,
+    student_folder: &str,
+    task: &str,
+    limits: &ResourceLimits,
+) -> Vec<TestResult> {
+    let mut results = vec![];
+    for test in tests.iter() {
+        let work_dir = format!(
+            "./rp_workspace/run/{}",
+            workspace_name(student_folder, task, &format!("test_{}", test.name))
+        );
+        let checker = test.checker.as_ref().and_then(|c| checkers.get(c)).map(|c| c.as_str());
+        let result = match run_test_case(run_command, test, checker, &work_dir, limits) {
+            Ok(r) => r,
+            Err(e) => {
+                println!("[TESTS] Error running test ({}) of task ({}) of ({}): {:#?}", test.name, task, student_folder, e);
+                failed_result(test, e.to_string())
+            },
+        };
+        results.push(result);
+    }
+    results
+}
+
+fn summarize_history(tests: &[TestCase], runs: Vec<CommitTestRun>) -> TestHistory {
+    let first_all_passing = runs
+        .iter()
+        .find(|run| run.passed.len() == tests.len())
+        .map(|run| run.commit.clone());
+
+    let mut regressions = vec![];
+    for test in tests.iter() {
+        // commit the test last passed in
+        let mut passed_in: Option<&str> = None;
+        for run in runs.iter() {
+            if run.passed.contains(&test.name) {
+                passed_in = Some(&run.commit);
+                continue;
+            }
+            if let Some(passed) = passed_in.take() {
+                regressions.push(TestRegression {
+                    test: test.name.clone(),
+                    passed_in: passed.to_string(),
+                    failed_in: run.commit.clone(),
+                });
+            }
+        }
+    }
+
+    TestHistory {
+        total: tests.len(),
+        runs,
+        first_all_passing,
+        regressions,
+    }
+}
+
+// command that runs an instructor program from any working directory. C and
+// C++ sources are compiled, anything else is run as an executable
+pub fn instructor_program_command(path: &str, limits: &ResourceLimits) -> Result<String, Error> {
+    let root = env::current_dir()?;
+    let compiler = match Path::new(path).extension().and_then(|e| e.to_str()) {
+        Some("c") => "gcc",
+        Some("cpp") | Some("cc") => "g++",
+        _ => return Ok(escape(&root.join(path).to_string_lossy())),
+    };
+
+    let binary = format!("./rp_workspace/bin/{}", workspace_name("instructor", path, "build"));
+    run_command_with_limits(&format!("{} -O2 -o {} {} -lm", compiler, binary, escape(path)), limits)?;
+    Ok(escape(&root.join(binary).to_string_lossy()))
+}
+
+// runs the program in a fresh working directory holding the files of the case
+pub fn run_test_case(
+    run_command: &str,
+    test: &TestCase,
+    checker: Option<&str>,
+    work_dir: &str,
+    limits: &ResourceLimits,
+) -> Result<TestResult, Error> {
+    let _ = fs::remove_dir_all(work_dir);
+    fs::create_dir_all(work_dir)?;
+    for file in test.files.iter() {
+        let name = match Path::new(file).file_name() {
+            Some(n) => n,
+            None => return Err(Error::new(ErrorKind::InvalidInput, format!("Not a file: {}", file))),
+        };
+        fs::copy(file, Path::new(work_dir).join(name))?;
+    }
+
+    let mut command = format!("cd {} && {}", escape(work_dir), run_command);
+    for arg in test.args.iter() {
+        command = format!("{} {}", command, escape(arg));
+    }
+    // paths of the config are relative to where the judge runs
+    if let Some(stdin) = &test.stdin {
+        let stdin = env::current_dir()?.join(stdin);
+        command = format!("{} < {}", command, escape(&stdin.to_string_lossy()));
+    }
+
+    let output = run_limited_command(&command, limits)?;
+    if output.timed_out {
+        let mut result = failed_result(test, "timed out".to_string());
+        result.timed_out = true;
+        return Ok(result);
+    }
+
+    if let Some(message) = check_output(test, checker.is_some(), &output)? {
+        return Ok(failed_result(test, message));
+    }
+    if let Some(checker) = checker {
+        return run_checker(checker, test, &output, work_dir, limits);
+    }
+    Ok(TestResult {
+        name: test.name.clone(),
+        passed: true,
+        score: test.weight,
+        weight: test.weight,
+        hidden: test.hidden,
+        timed_out: false,
+        message: None,
+    })
+}
+
+// testlib convention: "checker <input> <student output> <expected output>",
+// exit code 0 accepted, 1 wrong answer, 2 presentation error, 3 checker
+// failure, 4 extra output and 8 unexpected end of the output (both "wrong
+// output format" in testlib) and 7 partial points given as the first number
+// of the message
+fn run_checker(checker: &str, test: &TestCase, output: &CommandOutput, work_dir: &str, limits: &ResourceLimits) -> Result<TestResult, Error> {
+    let root = env::current_dir()?;
+    let student_output = root.join(work_dir).join(".rp_output");
+    fs::write(&student_output, &output.stdout)?;
+    let input = match &test.stdin {
+        Some(stdin) => root.join(stdin),
+        None => Path::new("/dev/null").to_path_buf(),
+    };
+    let expected = match &test.stdout {
+        Some(stdout) => root.join(stdout),
+        None => Path::new("/dev/null").to_path_buf(),
+    };
+
+    let command = format!(
+        "{} {} {} {}",
+        checker,
+        escape(&input.to_string_lossy()),
+        escape(&student_output.to_string_lossy()),
+        escape(&expected.to_string_lossy())
+    );
+    let verdict = run_limited_command(&command, limits)?;
+    if verdict.timed_out {
+        return Ok(failed_result(test, "checker timed out".to_string()));
+    }
+    // testlib writes its verdict to stderr
+    let message = if verdict.stderr.trim().is_empty() {
+        verdict.stdout.trim().to_string()
+    } else {
+        verdict.stderr.trim().to_string()
+    };
+
+    let share = match verdict.status {
+        Some(0) => 1.0,
+        Some(1) => return Ok(failed_result(test, format!("wrong answer: {}", message))),
+        Some(2 | 4 | 8) => return Ok(failed_result(test, format!("presentation error: {}", message))),
+        Some(7) => {
+            let points = Regex::new(r"^(?:points\s+)?([-+]?[0-9]*\.?[0-9]+(?:[eE][-+]?[0-9]+)?)").unwrap();
+            match points.captures(&message).and_then(|c| c[1].parse::<f64>().ok()) {
+                Some(p) => p.clamp(0.0, 1.0),
+                None => return Ok(failed_result(test, format!("checker gave no points: {}", message))),
+            }
+        },
+        status => {
+            println!("[TESTS] Checker failed on test ({}) with status {:?}: {}", test.name, status, message);
+            return Ok(failed_result(test, format!("checker failed: {}", message)));
+        },
+    };
+
+    Ok(TestResult {
+        name: test.name.clone(),
+        passed: share >= 1.0,
+        score: test.weight * share,
+        weight: test.weight,
+        hidden: test.hidden,
+        timed_out: false,
+        message: if message.is_empty() { None } else { Some(message) },
+    })
+}
+
+// reason the output fails the case, None if it passes. With a checker the
+// stdout is left to the checker
+fn check_output(test: &TestCase, has_checker: bool, output: &CommandOutput) -> Result<Option<String>, Error> {
+    let status = match output.status {
+        Some(s) => s,
+        None => return Ok(Some("killed by a signal".to_string())),
+    };
+    if let Some(expected) = test.exit_code {
+        if status != expected {
+            return Ok(Some(format!("exit code {}, expected {}", status, expected)));
+        }
+    }
+
+    let streams = [("stdout", &test.stdout, &output.stdout), ("stderr", &test.stderr, &output.stderr)];
+    for (stream, expected, actual) in streams.iter() {
+        if has_checker && *stream == "stdout" {
+            continue;
+        }
+        let expected = match expected {
+            Some(path) => fs::read_to_string(path)?,
+            None => continue,
+        };
+        if !compare_output(&expected, actual, &test.compare_mode()) {
+            return Ok(Some(match first_different_line(&expected, actual) {
+                Some(line) => format!("{} differs from line {}", stream, line),
+                None => format!("{} differs", stream),
+            }));
+        }
+    }
+    Ok(None)
+}
+
+pub fn compare_output(expected: &str, actual: &str, mode: &CompareMode) -> bool {
+    match mode {
+        CompareMode::Exact => expected == actual,
+        CompareMode::Whitespace => expected.split_whitespace().eq(actual.split_whitespace()),
+        CompareMode::CaseInsensitive => expected.to_lowercase() == actual.to_lowercase(),
+        CompareMode::Regex => match Regex::new(&format!("^(?:{})$", expected.trim_end())) {
+            Ok(regex) => regex.is_match(actual.trim_end()),
+            Err(e) => {
+                println!("[TESTS] Invalid expected output regex: {:#?}", e);
+                false
+            },
+        },
+        CompareMode::Float(tolerance) => {
+            let expected: Vec<&str> = expected.split_whitespace().collect();
+            let actual: Vec<&str> = actual.split_whitespace().collect();
+            expected.len() == actual.len() && expected
+                .iter()
+                .zip(actual.iter())
+                .all(|(e, a)| match (e.parse::<f64>(), a.parse::<f64>()) {
+                    (Ok(e), Ok(a)) => (e - a).abs() <= *tolerance,
+                    _ => e == a,
+                })
+        },
+        CompareMode::UnorderedLines => {
+            let mut expected = trimmed_lines(expected);
+            let mut actual = trimmed_lines(actual);
+            expected.sort();
+            actual.sort();
+            expected == actual
+        },
+    }
+}
+
+// lines without trailing whitespace and trailing empty lines
+fn trimmed_lines(text: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = text.lines().map(|l| l.trim_end()).collect();
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+    lines
+}
+
+pub fn first_different_line(expected: &str, actual: &str) -> Option<usize> {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    (0..expected.len().max(actual.len()))
+        .find(|i| expected.get(*i) != actual.get(*i))
+        .map(|i| i + 1)
+}
+
+fn failed_result(test: &TestCase, message: String) -> TestResult {
+    TestResult {
+        name: test.name.clone(),
+        passed: false,
+        score: 0.0,
+        weight: test.weight,
+        hidden: test.hidden,
+        timed_out: false,
+        message: Some(message),
+    }
+}
+
+fn save_test_history_to_submission(submission: &mut StudentProjectSubmission, task: &str, history: TestHistory) {
+    if let Some(task1) = submission.has_task1.clone() {
+        if task1 == task {
+            submission.test_history_task1 = Some(history);
+            return;
+        }
+    }
+
+    if let Some(task2) = submission.has_task2.clone() {
+        if task2 == task {
+            submission.test_history_task2 = Some(history);
+        }
+    }
+}
+
+fn save_test_results_to_submission(submission: &mut StudentProjectSubmission, task: &str, results: Vec<TestResult>) {
+    if let Some(task1) = submission.has_task1.clone() {
+        if task1 == task {
+            submission.test_results_task1 = Some(results);
+            return;
+        }
+    }
+
+    if let Some(task2) = submission.has_task2.clone() {
+        if task2 == task {
+            submission.test_results_task2 = Some(results);
+        }
+    }
+}
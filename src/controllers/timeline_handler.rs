@@ -0,0 +1,68 @@
+use chrono::{Duration, NaiveDate};
+
+use crate::models::{student_project::StudentProjectSubmission, config::AssignmentConfig, activity::Activity};
+
+
+// longest window used when the start is not configured
+const MAX_TIMELINE_DAYS: i64 = 60;
+
+// jordan's submissions only have the commits touching the student's folder,
+// not the activity of the whole shared repo
+pub fn compute_activity(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    let commit_days: Vec<NaiveDate> = submissions
+        .iter()
+        .filter(|s| s.cloned)
+        .flat_map(|s| s.commit_infos.iter().map(|c| c.committer_date.date_naive()))
+        .collect();
+
+    // the configured end, else the latest deadline, else the latest commit
+    let deadline = [config.task1.deadline(), config.task2.deadline()]
+        .into_iter()
+        .flatten()
+        .max()
+        .map(|d| d.date_naive());
+    let end = match config.timeline.end.or(deadline).or(commit_days.iter().max().copied()) {
+        Some(e) => e,
+        None => return,
+    };
+    // old commits, e.g. of a forked template, must not stretch the window
+    let start = match config.timeline.start {
+        Some(s) => s,
+        None => commit_days
+            .iter()
+            .min()
+            .copied()
+            .unwrap_or(end)
+            .max(end - Duration::days(MAX_TIMELINE_DAYS - 1)),
+    };
+    if start > end {
+        println!("[TIMELINE] Error: the timeline starts ({}) after it ends ({})", start, end);
+        return;
+    }
+    let days = (end - start).num_days() as usize + 1;
+
+    for submission in submissions.iter_mut() {
+        if !submission.cloned {
+            continue;
+        }
+        let mut commits_per_day = vec![0; days];
+        for commit in submission.commit_infos.iter() {
+            let day = (commit.committer_date.date_naive() - start).num_days();
+            // commits outside of the window are not shown
+            if day >= 0 && (day as usize) < days {
+                commits_per_day[day as usize] += 1;
+            }
+        }
+        submission.activity = Some(Activity { start, commits_per_day });
+    }
+}
+
+// one block character per day, scaled to the busiest day of the student
+pub fn sparkline(commits_per_day: &[usize]) -> String {
+    let blocks = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = commits_per_day.iter().copied().max().unwrap_or(0).max(1);
+    commits_per_day
+        .iter()
+        .map(|count| blocks[(count * 7).div_ceil(max)])
+        .collect()
+}
@@ -0,0 +1,170 @@
+use std::io::ErrorKind;
+
+use regex::Regex;
+
+use crate::models::{student_project::StudentProjectSubmission, config::AssignmentConfig, unit_test_result::UnitTestResult};
+
+use super::{
+    validator::{check_dir_exists, tasks_to_check},
+    config_handler::{get_task_config, get_task_language},
+    git_compilation_handler::binary_path,
+    os_helper::{run_command_with_limits, run_limited_command, CommandOutput},
+    parser::escape,
+};
+
+
+pub fn run_unit_tests(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[UNIT TESTS] Error reading repos directory!");
+        std::process::exit(1);
+    }
+
+    for submission in submissions.iter_mut() {
+        if !submission.cloned {
+            continue;
+        }
+
+        for task in tasks_to_check(submission).iter() {
+            let driver = match get_task_config(config, submission, task).and_then(|c| c.driver.clone()) {
+                Some(d) => d,
+                None => continue,
+            };
+            let language = get_task_language(config, submission, task);
+
+            let task_folder = format!("./rp_workspace/repos/{}/{}", submission.student_folder, task);
+            let task_path = format!("./rp_workspace/repos/{}/{}", escape(&submission.student_folder), escape(task));
+            let output = binary_path(&submission.student_folder, task, "driver");
+            let sources = language.find_source_files(&task_folder);
+            let build_command = match language.driver_build_command(&task_path, &sources, &escape(&driver), &output) {
+                Some(c) => c,
+                None => {
+                    println!("[UNIT TESTS] Test drivers are not supported for {} (task {})", language.name(), task);
+                    continue;
+                },
+            };
+
+            // a task that does not link with the driver fails every test
+            if let Err(e) = run_command_with_limits(&build_command, &language.limits(&config.compile_limits)) {
+                println!("[UNIT TESTS] Error building task ({}) of ({}) with the driver: {:#?}", task, submission.student_folder, e);
+                if e.kind() == ErrorKind::TimedOut {
+                    submission.timeouts.push(format!("unit test build {}", task));
+                }
+                save_unit_tests_to_submission(submission, task, vec![UnitTestResult {
+                    name: "build".to_string(),
+                    passed: false,
+                    message: Some(e.to_string()),
+                }]);
+                continue;
+            }
+
+            let run = match run_limited_command(&output, &language.limits(&config.run_limits)) {
+                Ok(o) => o,
+                Err(e) => {
+                    println!("[UNIT TESTS] Error running driver of task ({}) of ({}): {:#?}", task, submission.student_folder, e);
+                    continue;
+                },
+            };
+            if run.timed_out {
+                submission.timeouts.push(format!("unit tests {}", task));
+            }
+            let mut results = parse_driver_output(&run.stdout);
+            // a driver that crashed or failed never passes, whatever it printed
+            if let Some(message) = driver_failure(&run) {
+                results.push(UnitTestResult {
+                    name: "driver".to_string(),
+                    passed: false,
+                    message: Some(message),
+                });
+            }
+            save_unit_tests_to_submission(submission, task, results);
+        }
+    }
+}
+
+fn driver_failure(run: &CommandOutput) -> Option<String> {
+    if run.timed_out {
+        return Some("timed out".to_string());
+    }
+    match run.status {
+        Some(0) => None,
+        Some(status) => Some(format!("exit code {}", status)),
+        None => Some("killed by a signal".to_string()),
+    }
+}
+
+// understands TAP ("1..3", "ok 1 - name", "not ok 2 - name", "# diagnostic")
+// and the simple "PASS name" / "FAIL name message" protocol
+fn parse_driver_output(stdout: &str) -> Vec<UnitTestResult> {
+    let plan = Regex::new(r"^1\.\.(\d+)").unwrap();
+    let tap = Regex::new(r"^(not )?ok\b\s*(\d+)?\s*(?:-\s*)?([^#]*)").unwrap();
+    let simple = Regex::new(r"^(PASS|FAIL)\s+(\S+)\s*(.*)$").unwrap();
+
+    let mut results: Vec<UnitTestResult> = vec![];
+    let mut planned: Option<usize> = None;
+    for line in stdout.lines() {
+        let line = line.trim_end();
+        if let Some(capture) = plan.captures(line) {
+            planned = capture[1].parse().ok();
+            continue;
+        }
+
+        if let Some(capture) = tap.captures(line) {
+            let number = capture.get(2).map(|n| n.as_str()).unwrap_or("");
+            let name = capture[3].trim();
+            results.push(UnitTestResult {
+                name: if name.is_empty() { format!("test {}", number) } else { name.to_string() },
+                passed: capture.get(1).is_none(),
+                message: None,
+            });
+            continue;
+        }
+
+        if let Some(capture) = simple.captures(line) {
+            let message = capture[3].trim();
+            results.push(UnitTestResult {
+                name: capture[2].to_string(),
+                passed: &capture[1] == "PASS",
+                message: if message.is_empty() { None } else { Some(message.to_string()) },
+            });
+            continue;
+        }
+
+        // TAP diagnostics belong to the preceding test
+        if let Some(diagnostic) = line.trim_start().strip_prefix('#') {
+            if let Some(last) = results.last_mut() {
+                let message = last.message.get_or_insert_with(String::new);
+                if !message.is_empty() {
+                    message.push('\n');
+                }
+                message.push_str(diagnostic.trim());
+            }
+        }
+    }
+
+    // the driver crashed or stopped before finishing the plan
+    if let Some(planned) = planned {
+        for number in results.len() + 1..=planned {
+            results.push(UnitTestResult {
+                name: format!("test {}", number),
+                passed: false,
+                message: Some("not run".to_string()),
+            });
+        }
+    }
+    results
+}
+
+fn save_unit_tests_to_submission(submission: &mut StudentProjectSubmission, task: &str, results: Vec<UnitTestResult>) {
+    if let Some(task1) = submission.has_task1.clone() {
+        if task1 == task {
+            submission.unit_tests_task1 = Some(results);
+            return;
+        }
+    }
+
+    if let Some(task2) = submission.has_task2.clone() {
+        if task2 == task {
+            submission.unit_tests_task2 = Some(results);
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use std::{fs, io::ErrorKind};
+
+use regex::Regex;
+
+use crate::models::{student_project::StudentProjectSubmission, config::{AssignmentConfig, ResourceLimits}, valgrind_report::ValgrindReport};
+
+use super::{
+    validator::{check_dir_exists, tasks_to_check},
+    config_handler::{get_task_config, get_task_inputs, get_task_language},
+    git_compilation_handler::{build_task, binary_path, get_submission_main_file},
+    os_helper::run_limited_command,
+    parser::escape,
+};
+
+
+pub fn run_valgrind(submissions: &mut [StudentProjectSubmission], config: &AssignmentConfig) {
+    if !check_dir_exists("rp_workspace/repos") {
+        println!("[VALGRIND] Error reading repos directory!");
+        std::process::exit(1);
+    }
+
+    // programs run an order of magnitude slower under valgrind and it maps
+    // its own memory on top of the program's
+    let run_limits = ResourceLimits::new(config.run_limits.timeout_secs * 10, None);
+
+    for submission in submissions.iter_mut() {
+        if !submission.cloned {
+            continue;
+        }
+
+        for task in tasks_to_check(submission).iter() {
+            let task_main_file = match get_submission_main_file(submission, task) {
+                Some(main) => main,
+                None => continue,
+            };
+            let task_config = match get_task_config(config, submission, task) {
+                Some(c) => c,
+                None => continue,
+            };
+            // only native binaries can be instrumented
+            let language = get_task_language(config, submission, task);
+            if !language.is_native() {
+                continue;
+            }
+
+            let binary = match build_task(
+                language.as_ref(),
+                &submission.student_folder,
+                task,
+                &task_main_file,
+                "-g -O0",
+                "valgrind",
+                &config.compile_limits
+            ) {
+                Ok(b) => b,
+                Err(e) => {
+                    println!("[VALGRIND] Error building task ({}) of ({}): {:#?}", task, submission.student_folder, e);
+                    if e.kind() == ErrorKind::TimedOut {
+                        submission.timeouts.push(format!("valgrind build {}", task));
+                    }
+                    continue;
+                },
+            };
+
+            let xml_file = format!("{}.xml", binary_path(&submission.student_folder, task, "valgrind"));
+            let mut report = ValgrindReport::default();
+            let mut was_checked = false;
+            for input in get_task_inputs(task_config).iter() {
+                let _ = fs::remove_file(&xml_file);
+                let mut command = format!("valgrind --leak-check=full --xml=yes --xml-file={} {}", xml_file, binary);
+                if let Some(input) = input {
+                    command = format!("{} < {}", command, escape(input));
+                }
+
+                let output = match run_limited_command(&command, &run_limits) {
+                    Ok(o) => o,
+                    Err(e) => {
+                        println!("[VALGRIND] Error running task ({}) of ({}): {:#?}", task, submission.student_folder, e);
+                        continue;
+                    },
+                };
+                if output.timed_out {
+                    submission.timeouts.push(format!("valgrind run {}", task));
+                    continue;
+                }
+
+                let xml = match fs::read_to_string(&xml_file) {
+                    Ok(x) => x,
+                    Err(e) => {
+                        println!("[VALGRIND] Error reading valgrind output of task ({}) of ({}): {:#?}", task, submission.student_folder, e);
+                        continue;
+                    },
+                };
+                // keep the worst run of each measure
+                let run_report = parse_valgrind_xml(&xml);
+                report.definitely_lost = report.definitely_lost.max(run_report.definitely_lost);
+                report.indirectly_lost = report.indirectly_lost.max(run_report.indirectly_lost);
+                report.possibly_lost = report.possibly_lost.max(run_report.possibly_lost);
+                report.invalid_reads = report.invalid_reads.max(run_report.invalid_reads);
+                report.invalid_writes = report.invalid_writes.max(run_report.invalid_writes);
+                was_checked = true;
+            }
+            if was_checked {
+                save_valgrind_report_to_submission(submission, task, report);
+            }
+        }
+    }
+}
+
+fn parse_valgrind_xml(xml: &str) -> ValgrindReport {
+    let error = Regex::new(r"(?s)<error>(.*?)</error>").unwrap();
+    let kind = Regex::new(r"<kind>(\w+)</kind>").unwrap();
+    let leaked_bytes = Regex::new(r"<leakedbytes>(\d+)</leakedbytes>").unwrap();
+
+    let mut report = ValgrindReport::default();
+    for capture in error.captures_iter(xml) {
+        let block = &capture[1];
+        let kind = match kind.captures(block) {
+            Some(k) => k[1].to_string(),
+            None => continue,
+        };
+        let bytes = leaked_bytes
+            .captures(block)
+            .and_then(|b| b[1].parse::<u64>().ok())
+            .unwrap_or(0);
+
+        match kind.as_str() {
+            "Leak_DefinitelyLost" => report.definitely_lost += bytes,
+            "Leak_IndirectlyLost" => report.indirectly_lost += bytes,
+            "Leak_PossiblyLost" => report.possibly_lost += bytes,
+            "InvalidRead" => report.invalid_reads += 1,
+            "InvalidWrite" => report.invalid_writes += 1,
+            _ => (),
+        }
+    }
+    report
+}
+
+fn save_valgrind_report_to_submission(submission: &mut StudentProjectSubmission, task: &str, report: ValgrindReport) {
+    if let Some(task1) = submission.has_task1.clone() {
+        if task1 == task {
+            submission.valgrind_task1 = Some(report);
+            return;
+        }
+    }
+
+    if let Some(task2) = submission.has_task2.clone() {
+        if task2 == task {
+            submission.valgrind_task2 = Some(report);
+        }
+    }
+}
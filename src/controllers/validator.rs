@@ -1,4 +1,4 @@
-use std::{fs, path::Path};
+use std::fs;
 
 use crate::models::student_project::StudentProjectSubmission;
 
@@ -30,31 +30,6 @@ pub fn check_dir_exists(dir_name: &str) -> bool {
     wd_meta.is_dir()
 }
 
-pub fn find_main_file(folder_name: &str) -> Option<String> {
-    let path = Path::new(folder_name);
-    if !path.is_dir() {
-        return None;
-    }
-
-    for entry in fs::read_dir(path).unwrap() {
-        let entry = entry.unwrap();
-        let file_name = entry.file_name().into_string().unwrap();
-        if file_name == "main.c" {
-            return Some(file_name);
-        }
-    }
-
-    for entry in fs::read_dir(path).unwrap() {
-        let entry = entry.unwrap();
-        let file_name = entry.file_name().into_string().unwrap();
-        if file_name.ends_with(".c") {
-            return Some(file_name);
-        }
-    }
-
-    None
-}
-
 pub fn find_accepted_folder(folder_name: &str, accepted_names: &[&str]) -> Option<String> {
     let folders = match folder_names(folder_name) {
         Ok(f) => f,
@@ -60,6 +60,51 @@ pub fn setup_workdir() {
         println!("[WD] Error clearing moss directory!\n{:#?}", e);
         std::process::exit(1);
     };
+
+    // make folder for binaries built by the judge
+    if !check_dir_exists("rp_workspace/bin") {
+        println!("[WD] Creating bin folder!");
+        if let Err(e) = run_command("mkdir rp_workspace/bin") {
+            println!("[WD] Error creating bin directory!\n{:#?}", e);
+            std::process::exit(1);
+        };
+    }
+
+    // clear bin folder
+    if let Err(e) = run_command("rm -rf rp_workspace/bin/*") {
+        println!("[WD] Error clearing bin directory!\n{:#?}", e);
+        std::process::exit(1);
+    };
+
+    // make folder for the working directories of test runs
+    if !check_dir_exists("rp_workspace/run") {
+        println!("[WD] Creating run folder!");
+        if let Err(e) = run_command("mkdir rp_workspace/run") {
+            println!("[WD] Error creating run directory!\n{:#?}", e);
+            std::process::exit(1);
+        };
+    }
+
+    // clear run folder
+    if let Err(e) = run_command("rm -rf rp_workspace/run/*") {
+        println!("[WD] Error clearing run directory!\n{:#?}", e);
+        std::process::exit(1);
+    };
+
+    // make folder for the per-student feedback files
+    if !check_dir_exists("rp_workspace/feedback") {
+        println!("[WD] Creating feedback folder!");
+        if let Err(e) = run_command("mkdir rp_workspace/feedback") {
+            println!("[WD] Error creating feedback directory!\n{:#?}", e);
+            std::process::exit(1);
+        };
+    }
+
+    // clear feedback folder
+    if let Err(e) = run_command("rm -rf rp_workspace/feedback/*") {
+        println!("[WD] Error clearing feedback directory!\n{:#?}", e);
+        std::process::exit(1);
+    };
 }
 
 pub fn move_sources(sources: &Vec<FilePath>) {
@@ -116,6 +161,7 @@ pub fn move_sources(sources: &Vec<FilePath>) {
                 };
             },
             FilePath::GitHub(_) => (),
+            FilePath::Config(_) => (),
         }
     }
 }
@@ -5,11 +5,30 @@ use models::student_project::StudentProjectSubmission;
 
 use crate::controllers::exporter::export_to_xlsx;
 use crate::controllers::git_clone_handler::clone_repos;
-use crate::controllers::git_commit_handler::{extract_commits, check_structure, check_latest_commit_date};
-use crate::controllers::git_compilation_handler::compile_commits;
+use crate::controllers::git_commit_handler::{extract_commits, check_structure, check_latest_commit_date, check_commit_timestamps};
+use crate::controllers::git_compilation_handler::{compile_commits, check_standards};
 use crate::controllers::moss_handler::setup_moss_folders;
 use crate::controllers::parser::{parse_file_args, escape};
 use crate::controllers::workdir::setup_workdir;
+use crate::controllers::config_handler::load_config;
+use crate::controllers::sanitizer_handler::run_sanitizers;
+use crate::controllers::valgrind_handler::run_valgrind;
+use crate::controllers::rule_checker::check_rules;
+use crate::controllers::metrics_handler::compute_metrics;
+use crate::controllers::style_handler::check_style;
+use crate::controllers::feedback_handler::write_feedback;
+use crate::controllers::unit_test_handler::run_unit_tests;
+use crate::controllers::test_handler::{run_tests, run_test_history};
+use crate::controllers::differential_handler::run_differential_tests;
+use crate::controllers::benchmark_handler::run_benchmarks;
+use crate::controllers::coverage_handler::run_coverage;
+use crate::controllers::authorship_handler::check_authorship;
+use crate::controllers::branch_handler::select_branches;
+use crate::controllers::shared_history_handler::detect_shared_history;
+use crate::controllers::commit_quality_handler::check_commit_quality;
+use crate::controllers::timeline_handler::compute_activity;
+use crate::controllers::hygiene_handler::check_hygiene;
+use crate::controllers::deadline_handler::{check_deadlines, checkout_deadline_snapshots, checkout_task_snapshots, compare_with_head};
 use crate::models::file_path::FilePath;
 
 mod controllers;
@@ -19,11 +38,16 @@ mod models;
 fn main() {
     let args: Vec<String> = env::args().collect();
     let file_paths: Vec<FilePath> = parse_file_args(args);
-    
+
+    // instructor programs are built into the workspace while loading the config
     println!("[MAIN] Setting up workspace...");
     setup_workdir();
     println!("\tDone!");
 
+    println!("[MAIN] Loading assignment config...");
+    let config = load_config(&file_paths);
+    println!("\tDone!");
+
     println!("[MAIN] Moving sources to workspace...");
     move_sources(&file_paths);
     println!("\tDone!");
@@ -33,11 +57,29 @@ fn main() {
     println!("\tDone!");
         
     println!("[MAIN] Cloning git repos...");
-    clone_repos(&mut submissions, &file_paths);
+    clone_repos(&mut submissions, &file_paths, &config);
+    println!("\tDone!");
+
+    println!("[MAIN] Selecting branches...");
+    select_branches(&mut submissions, &config);
     println!("\tDone!");
+
+    println!("[MAIN] Detecting shared commit history...");
+    detect_shared_history(&mut submissions, &config);
+    println!("\tDone!");
+
+    if config.judge_at_deadline {
+        println!("[MAIN] Checking out deadline snapshots...");
+        checkout_deadline_snapshots(&mut submissions, &config);
+        println!("\tDone!");
+    }
     
     println!("[MAIN] Checking git repo structure...");
-    check_structure(&mut submissions);
+    check_structure(&mut submissions, &config);
+    println!("\tDone!");
+
+    println!("[MAIN] Checking repo hygiene...");
+    check_hygiene(&mut submissions, &config);
     println!("\tDone!");
     
     println!("[MAIN] Extracting commits...");
@@ -48,14 +90,108 @@ fn main() {
     check_latest_commit_date(&mut submissions);
     println!("\tDone!");
 
+    println!("[MAIN] Checking commit timestamps...");
+    check_commit_timestamps(&mut submissions, &config);
+    println!("\tDone!");
+
+    println!("[MAIN] Checking commit authorship...");
+    check_authorship(&mut submissions, &config);
+    println!("\tDone!");
+
+    println!("[MAIN] Checking task deadlines...");
+    check_deadlines(&mut submissions, &config);
+    println!("\tDone!");
+
+    println!("[MAIN] Rating commit quality...");
+    check_commit_quality(&mut submissions, &config);
+    println!("\tDone!");
+
+    println!("[MAIN] Computing commit activity...");
+    compute_activity(&mut submissions, &config);
+    println!("\tDone!");
+
+    if config.judge_at_deadline {
+        println!("[MAIN] Rolling back tasks with earlier deadlines...");
+        checkout_task_snapshots(&mut submissions, &config);
+        println!("\tDone!");
+    }
+
     println!("[MAIN] Compiling commits...");
-    compile_commits(&mut submissions);
+    compile_commits(&mut submissions, &config);
     println!("\tDone!");
 
+    println!("[MAIN] Checking C standard compatibility...");
+    check_standards(&mut submissions, &config);
+    println!("\tDone!");
+
+    println!("[MAIN] Checking assignment rules...");
+    check_rules(&mut submissions, &config);
+    println!("\tDone!");
+
+    println!("[MAIN] Computing code metrics...");
+    compute_metrics(&mut submissions, &config);
+    println!("\tDone!");
+
+    if config.style {
+        println!("[MAIN] Checking code style...");
+        check_style(&mut submissions, &config);
+        println!("\tDone!");
+    }
+
+    println!("[MAIN] Running instructor unit tests...");
+    run_unit_tests(&mut submissions, &config);
+    println!("\tDone!");
+
+    println!("[MAIN] Running test cases...");
+    run_tests(&mut submissions, &config);
+    println!("\tDone!");
+
+    if config.test_history {
+        println!("[MAIN] Running test cases on every commit...");
+        run_test_history(&mut submissions, &config);
+        println!("\tDone!");
+    }
+
+    println!("[MAIN] Comparing against the reference solution on generated inputs...");
+    run_differential_tests(&mut submissions, &config);
+    println!("\tDone!");
+
+    println!("[MAIN] Benchmarking against the reference solution...");
+    run_benchmarks(&mut submissions, &config);
+    println!("\tDone!");
+
+    if config.sanitizer {
+        println!("[MAIN] Running sanitizer builds...");
+        run_sanitizers(&mut submissions, &config);
+        println!("\tDone!");
+    }
+
+    if config.coverage {
+        println!("[MAIN] Collecting test coverage...");
+        run_coverage(&mut submissions, &config);
+        println!("\tDone!");
+    }
+
+    if config.valgrind {
+        println!("[MAIN] Running valgrind leak checks...");
+        run_valgrind(&mut submissions, &config);
+        println!("\tDone!");
+    }
+
     println!("[MAIN] Preparing moss submission folders");
     setup_moss_folders(&submissions);
     println!("\nDone!");
 
+    if config.judge_at_deadline {
+        println!("[MAIN] Comparing deadline snapshots with HEAD...");
+        compare_with_head(&mut submissions, &config);
+        println!("\tDone!");
+    }
+
+    println!("[MAIN] Writing student feedback...");
+    write_feedback(&submissions);
+    println!("\tDone!");
+
     println!("[MAIN] Exporting submissions...");
     match export_to_xlsx(submissions, "./rp_workspace/results.xlsx") {
         Ok(_) => println!("\tDone!"),
@@ -0,0 +1,8 @@
+use chrono::NaiveDate;
+
+// commits per day over the assignment window, the same window for every submission
+#[derive(Debug, Clone)]
+pub struct Activity {
+    pub start: NaiveDate,
+    pub commits_per_day: Vec<usize>,
+}
@@ -0,0 +1,21 @@
+use super::commit_info::Identity;
+
+#[derive(Debug, Clone)]
+pub struct IdentityShare {
+    pub identity: Identity,
+    pub authored: usize,
+    pub committed: usize,
+    pub co_authored: usize,
+    // percent of the commits authored by the identity
+    pub share: f64,
+    // None if the student's email and username are unknown
+    pub matches_student: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AuthorshipReport {
+    // most authored commits first
+    pub identities: Vec<IdentityShare>,
+    // commits authored by identities that do not match the student
+    pub foreign_commits: Vec<String>,
+}
@@ -0,0 +1,12 @@
+#[derive(Debug, Clone)]
+pub struct BenchmarkResult {
+    // sum over the benchmark inputs of the median user + system time
+    pub cpu_secs: f64,
+    // highest median peak resident set size of the inputs
+    pub peak_rss_kb: u64,
+    // student divided by the reference solution
+    pub time_ratio: f64,
+    pub memory_ratio: f64,
+    // one of the ratios is above the configured maximum
+    pub too_slow: bool,
+}
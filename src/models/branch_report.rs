@@ -0,0 +1,10 @@
+#[derive(Debug, Clone, Default)]
+pub struct BranchReport {
+    // remote branches without the "origin/" prefix
+    pub branches: Vec<String>,
+    pub tags: Vec<String>,
+    // branch or tag checked out for the later stages
+    pub judged: String,
+    // configured ref the repo does not have, the default branch is judged instead
+    pub missing_ref: Option<String>,
+}
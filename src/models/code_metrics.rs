@@ -0,0 +1,22 @@
+#[derive(Debug, Clone, Default)]
+pub struct CodeMetrics {
+    // all lines of the sources
+    pub physical_loc: usize,
+    // statements: semicolons outside of parentheses and preprocessor lines
+    pub logical_loc: usize,
+    // lines with a comment on them
+    pub comment_lines: usize,
+    pub functions: usize,
+    pub max_complexity: usize,
+    pub average_complexity: f64,
+    // deepest block nesting inside a function body
+    pub max_nesting: usize,
+    pub longest_function: Option<String>,
+    pub longest_function_lines: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct CommitMetrics {
+    pub commit: String,
+    pub metrics: CodeMetrics,
+}
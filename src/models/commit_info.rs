@@ -0,0 +1,21 @@
+use chrono::{DateTime, FixedOffset};
+
+#[derive(Debug, Clone)]
+pub struct CommitInfo {
+    // abbreviated hashes, like the commit lists of the tasks
+    pub hash: String,
+    // in parent order, the first parent is the branch that was committed on
+    pub parents: Vec<String>,
+    pub author_date: DateTime<FixedOffset>,
+    pub committer_date: DateTime<FixedOffset>,
+    pub author: Identity,
+    pub committer: Identity,
+    // from Co-authored-by trailers
+    pub co_authors: Vec<Identity>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Identity {
+    pub name: String,
+    pub email: String,
+}
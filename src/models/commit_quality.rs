@@ -0,0 +1,19 @@
+#[derive(Debug, Clone, Default)]
+pub struct CommitQuality {
+    pub commits: usize,
+    // of the subject lines
+    pub median_message_length: usize,
+    pub short_messages: usize,
+    // empty or one of the configured default messages
+    pub default_messages: usize,
+    // added plus deleted lines inside the task folder
+    pub median_lines_changed: usize,
+    // percent of all added lines that the largest commit added
+    pub largest_commit_share: f64,
+    // set if the largest commit added more than the configured share
+    pub big_bang_commit: Option<String>,
+    // between the first and the last commit
+    pub spread_days: f64,
+    // 0 to 100
+    pub score: f64,
+}
@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, TimeZone};
+
+use super::test_case::{TestCase, CompareMode, resolve_compare};
+
+#[derive(Debug, Clone)]
+pub struct ResourceLimits {
+    // wall-clock limit for the whole process group
+    pub timeout_secs: u64,
+    // virtual memory limit applied with ulimit, None means unlimited
+    pub memory_mb: Option<u64>,
+}
+
+impl ResourceLimits {
+    pub fn new(timeout_secs: u64, memory_mb: Option<u64>) -> Self {
+        Self { 
+            timeout_secs, 
+            memory_mb,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TaskConfig {
+    // c, cpp, python or java
+    pub language: String,
+    // stdin files the programs of the task are run with
+    pub inputs: Vec<String>,
+    pub rules: TaskRules,
+    // instructor test driver linked against the task sources
+    pub driver: Option<String>,
+    pub tests: Vec<TestCase>,
+    // folder with NAME.in, NAME.out, NAME.err and NAME.args files
+    pub tests_dir: Option<String>,
+    // defaults for tests that do not set their own
+    pub compare: Option<CompareMode>,
+    pub tolerance: Option<f64>,
+    pub checker: Option<String>,
+    // instructor solution, C/C++ source or executable
+    pub reference: Option<String>,
+    // prints a random input for the seed it gets as its only argument
+    pub generator: Option<String>,
+    pub random_tests: usize,
+    pub random_seed: u64,
+    // remove lines of a failing input while it keeps failing and the
+    // validator accepts it
+    pub shrink: bool,
+    // exits with 0 if the input on its stdin is well-formed
+    pub validator: Option<String>,
+    // large stdin files the final build and the reference are timed on
+    pub benchmark_inputs: Vec<String>,
+    pub benchmark_runs: usize,
+    // multiple of the reference time and memory a submission may use
+    pub max_ratio: Option<f64>,
+    // local time in the timezone below
+    pub deadline: Option<NaiveDateTime>,
+    pub timezone: FixedOffset,
+    pub grace_minutes: i64,
+}
+
+impl TaskConfig {
+    // last moment a commit counts as on time, grace period included
+    pub fn deadline(&self) -> Option<DateTime<FixedOffset>> {
+        let local = self.timezone.from_local_datetime(&self.deadline?).single()?;
+        Some(local + Duration::minutes(self.grace_minutes))
+    }
+
+    pub fn compare_mode(&self) -> CompareMode {
+        resolve_compare(self.compare.as_ref(), self.tolerance)
+    }
+}
+
+impl Default for TaskConfig {
+    fn default() -> Self {
+        Self { 
+            language: "c".to_string(),
+            inputs: vec![],
+            rules: TaskRules::default(),
+            driver: None,
+            tests: vec![],
+            tests_dir: None,
+            compare: None,
+            tolerance: None,
+            checker: None,
+            reference: None,
+            generator: None,
+            random_tests: 20,
+            random_seed: 1,
+            shrink: false,
+            validator: None,
+            benchmark_inputs: vec![],
+            benchmark_runs: 5,
+            max_ratio: None,
+            deadline: None,
+            timezone: FixedOffset::east_opt(0).unwrap(),
+            grace_minutes: 0,
+        }
+    }
+}
+
+// constructs the task sources must or must not contain
+#[derive(Debug, Clone, Default)]
+pub struct TaskRules {
+    pub banned_headers: Vec<String>,
+    pub banned_functions: Vec<String>,
+    // plain names ("fib") or signatures ("int fib(int)")
+    pub required_functions: Vec<String>,
+    pub required_keywords: Vec<String>,
+    pub require_recursion: bool,
+    // per source file
+    pub max_lines: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AssignmentConfig {
+    pub clone_limits: ResourceLimits,
+    pub compile_limits: ResourceLimits,
+    pub run_limits: ResourceLimits,
+    pub task1: TaskConfig,
+    pub task2: TaskConfig,
+
+    // optional stages
+    pub sanitizer: bool,
+    pub valgrind: bool,
+    // run the test cases on every commit of a task
+    pub test_history: bool,
+    pub coverage: bool,
+    // compute the code metrics on every commit of a task
+    pub metrics_history: bool,
+    pub style: bool,
+    pub style_config: StyleConfig,
+    // check out the last commit before the deadlines instead of judging HEAD
+    pub judge_at_deadline: bool,
+    pub timestamp_config: TimestampConfig,
+    pub roster: Vec<RosterEntry>,
+    pub commit_quality: CommitQualityConfig,
+    pub timeline: TimelineConfig,
+    pub git: GitConfig,
+    // hash prefixes of commits every repo may share, e.g. of a template repo
+    pub ignore_shared_commits: Vec<String>,
+    pub hygiene: HygieneConfig,
+    // configured path of an instructor program (generator, reference,
+    // validator, checker) to the command that runs it, built when loading
+    pub instructor_programs: HashMap<String, String>,
+}
+
+// what the hygiene stage flags, patterns match file and folder names with
+// '*' as a wildcard
+#[derive(Debug, Clone)]
+pub struct HygieneConfig {
+    pub binaries: Vec<String>,
+    pub ide_folders: Vec<String>,
+    pub junk: Vec<String>,
+    // also flag ELF, Mach-O and PE files whatever their name
+    pub detect_executables: bool,
+    // 0 disables the size check
+    pub max_file_kb: u64,
+    pub require_gitignore: bool,
+    // also flag files that were committed and removed again
+    pub history: bool,
+}
+
+// which branch or tag of a repo is judged
+#[derive(Debug, Clone, Default)]
+pub struct GitConfig {
+    pub git_ref: Option<String>,
+    // judge the branch with the latest commit touching a task folder
+    pub all_branches: bool,
+}
+
+// days shown in the timeline sheet, both included
+#[derive(Debug, Clone, Default)]
+pub struct TimelineConfig {
+    pub start: Option<NaiveDate>,
+    pub end: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CommitQualityConfig {
+    // lowercase subjects that say nothing about the change
+    pub default_messages: Vec<String>,
+    pub min_message_length: usize,
+    // fewer commits lower the granularity part of the score
+    pub min_commits: usize,
+    // percent of the added lines above which a single commit is a big bang
+    pub big_bang_share: f64,
+    // fewer days between the first and the last commit lower the spread part
+    pub min_spread_days: f64,
+    pub message_weight: f64,
+    pub granularity_weight: f64,
+    pub spread_weight: f64,
+}
+
+// a student as listed in the roster file
+#[derive(Debug, Clone)]
+pub struct RosterEntry {
+    // start of the submission folder, the name Moodle puts before the first '_'
+    pub name: String,
+    pub email: Option<String>,
+    // GitHub/GitLab username
+    pub username: Option<String>,
+}
+
+// thresholds above which commit timestamps are flagged
+#[derive(Debug, Clone)]
+pub struct TimestampConfig {
+    // between the author and the committer date of a commit
+    pub max_author_gap_hours: i64,
+    // commits sharing one committer date
+    pub max_identical: usize,
+    // change of the UTC offset from the first parent
+    pub max_timezone_jump_hours: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct StyleConfig {
+    // style passed to clang-format --style, the built-in checks if None
+    pub clang_format: Option<String>,
+    pub max_line_length: usize,
+}
+
+impl Default for AssignmentConfig {
+    fn default() -> Self {
+        Self { 
+            clone_limits: ResourceLimits::new(300, None),
+            compile_limits: ResourceLimits::new(30, Some(1024)),
+            run_limits: ResourceLimits::new(10, Some(512)),
+            task1: TaskConfig::default(),
+            task2: TaskConfig::default(),
+            sanitizer: false,
+            valgrind: false,
+            test_history: false,
+            coverage: false,
+            metrics_history: false,
+            style: false,
+            judge_at_deadline: false,
+            style_config: StyleConfig {
+                clang_format: None,
+                max_line_length: 100,
+            },
+            timestamp_config: TimestampConfig {
+                max_author_gap_hours: 72,
+                max_identical: 3,
+                max_timezone_jump_hours: 3,
+            },
+            roster: vec![],
+            commit_quality: CommitQualityConfig {
+                default_messages: ["update", "a", "initial commit", "commit", "fix", "changes", "wip", "."]
+                    .iter()
+                    .map(|m| m.to_string())
+                    .collect(),
+                min_message_length: 10,
+                min_commits: 5,
+                big_bang_share: 70.0,
+                min_spread_days: 3.0,
+                message_weight: 1.0,
+                granularity_weight: 1.0,
+                spread_weight: 1.0,
+            },
+            timeline: TimelineConfig::default(),
+            git: GitConfig::default(),
+            ignore_shared_commits: vec![],
+            instructor_programs: HashMap::new(),
+            hygiene: HygieneConfig {
+                binaries: ["a.out", "*.exe", "*.o", "*.obj"].iter().map(|p| p.to_string()).collect(),
+                ide_folders: [".vscode", ".idea", "cmake-build-*"].iter().map(|p| p.to_string()).collect(),
+                junk: [".DS_Store", "Thumbs.db", "desktop.ini"].iter().map(|p| p.to_string()).collect(),
+                detect_executables: true,
+                max_file_kb: 1024,
+                require_gitignore: true,
+                history: false,
+            },
+        }
+    }
+}
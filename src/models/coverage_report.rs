@@ -0,0 +1,10 @@
+// gcov summary of the student's own files
+#[derive(Debug, Clone, Default)]
+pub struct CoverageReport {
+    pub lines_covered: usize,
+    pub lines_total: usize,
+    // branches taken at least once
+    pub branches_taken: usize,
+    pub branches_total: usize,
+    pub uncovered_functions: Vec<String>,
+}
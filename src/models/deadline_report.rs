@@ -0,0 +1,8 @@
+#[derive(Debug, Clone)]
+pub struct DeadlineReport {
+    // newest commit of the task committed before the deadline and grace period
+    pub last_commit_before: Option<String>,
+    pub commits_after: usize,
+    // started days between the deadline and the newest late commit
+    pub late_days: i64,
+}
@@ -0,0 +1,19 @@
+#[derive(Debug, Clone)]
+pub struct DifferentialResult {
+    pub runs: usize,
+    pub failed_runs: usize,
+    // first failing run, shrunk if enabled
+    pub failure: Option<DifferentialFailure>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DifferentialFailure {
+    // seed the generator was called with
+    pub seed: u64,
+    pub input: String,
+    // output of the reference solution
+    pub expected: String,
+    pub actual: String,
+    pub message: String,
+    pub shrunk: bool,
+}
@@ -2,4 +2,5 @@
 pub enum FilePath {
     Zip(String),
     GitHub(String),
+    Config(String),
 }
\ No newline at end of file
@@ -0,0 +1,10 @@
+// the final state of a task at the current HEAD, for comparison with the
+// results of the deadline snapshot
+#[derive(Debug, Clone)]
+pub struct HeadResult {
+    // task folder at HEAD, None if it does not exist there
+    pub folder: Option<String>,
+    pub final_commit_compile: bool,
+    // None if the task has no test cases
+    pub tests_passed: Option<usize>,
+}
@@ -0,0 +1,10 @@
+#[derive(Debug, Clone)]
+pub struct HygieneFinding {
+    // binary, ide_folder, junk, large_file or no_gitignore
+    pub kind: String,
+    // relative to the repo root, empty for no_gitignore
+    pub path: String,
+    // commit that added a file that is no longer in the judged commit
+    pub commit: Option<String>,
+    pub message: String,
+}
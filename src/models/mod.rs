@@ -1,2 +1,25 @@
 pub mod file_path;
-pub mod student_project;
\ No newline at end of file
+pub mod student_project;
+pub mod standard_report;
+pub mod config;
+pub mod sanitizer_finding;
+pub mod valgrind_report;
+pub mod rule_violation;
+pub mod unit_test_result;
+pub mod test_case;
+pub mod differential_result;
+pub mod test_history;
+pub mod benchmark_result;
+pub mod coverage_report;
+pub mod code_metrics;
+pub mod style_report;
+pub mod deadline_report;
+pub mod head_result;
+pub mod commit_info;
+pub mod timestamp_anomaly;
+pub mod authorship_report;
+pub mod commit_quality;
+pub mod activity;
+pub mod branch_report;
+pub mod shared_history;
+pub mod hygiene_finding;
\ No newline at end of file
@@ -0,0 +1,8 @@
+#[derive(Debug, Clone)]
+pub struct RuleViolation {
+    // config key of the violated rule, e.g. banned_functions
+    pub rule: String,
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    pub message: String,
+}
@@ -0,0 +1,11 @@
+#[derive(Debug, Clone)]
+pub struct SanitizerFinding {
+    // e.g. heap-buffer-overflow, heap-use-after-free, signed integer overflow
+    pub kind: String,
+    // input file the program was run with
+    pub input: Option<String>,
+    // innermost stack frame that lies in the student's files
+    pub function: Option<String>,
+    pub file: Option<String>,
+    pub line: Option<i32>,
+}
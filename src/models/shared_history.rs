@@ -0,0 +1,14 @@
+// a group of submissions whose repos share commits, seen from one member
+#[derive(Debug, Clone)]
+pub struct SharedHistory {
+    // numbered from 1 in the order of the submissions
+    pub group: usize,
+    // commits that at least two members of the group have
+    pub shared_commits: usize,
+    // shared commits without a parent, a shared root means a common start
+    pub shared_roots: usize,
+    // oldest shared commit by committer date
+    pub first_commit: String,
+    pub first_author: String,
+    pub first_date: String,
+}
@@ -0,0 +1,16 @@
+#[derive(Debug, Clone)]
+pub struct StandardConstruct {
+    // standard under which the construct is rejected
+    pub standard: String,
+    pub line: Option<i32>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct StandardReport {
+    // standards (oldest to newest) the final commit compiles cleanly with
+    pub clean_standards: Vec<String>,
+    pub minimum_standard: Option<String>,
+    // diagnostics of standards older than the minimum one
+    pub constructs: Vec<StandardConstruct>,
+}
@@ -1,18 +1,34 @@
-use chrono::{NaiveDateTime};
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+use super::{standard_report::StandardReport, sanitizer_finding::SanitizerFinding, valgrind_report::ValgrindReport, rule_violation::RuleViolation, unit_test_result::UnitTestResult, test_case::TestResult, differential_result::DifferentialResult, test_history::TestHistory, benchmark_result::BenchmarkResult, coverage_report::CoverageReport, code_metrics::{CodeMetrics, CommitMetrics}, style_report::StyleReport, deadline_report::DeadlineReport, head_result::HeadResult, commit_info::CommitInfo, timestamp_anomaly::TimestampAnomaly, authorship_report::AuthorshipReport, commit_quality::CommitQuality, activity::Activity, branch_report::BranchReport, shared_history::SharedHistory, hygiene_finding::HygieneFinding};
 
 #[derive(Debug)] 
 pub struct StudentProjectSubmission {
     pub student_folder: String,
     pub git_repo: Option<String>,
+    // other submissions that point at the same repo
+    pub same_repo_as: Vec<String>,
     pub cloned: bool,
     pub jordan: bool,
+    // stages that were killed for exceeding the resource limits
+    pub timeouts: Vec<String>,
+    pub branch_report: Option<BranchReport>,
+    pub shared_history: Option<SharedHistory>,
+    pub hygiene: Option<Vec<HygieneFinding>>,
 
-    pub gcc_standard: Option<String>,
     pub last_commit_date: Option<NaiveDateTime>,
+    pub cloned_at: Option<DateTime<Utc>>,
+    // every commit reachable from HEAD, newest first
+    pub commit_infos: Vec<CommitInfo>,
+    pub timestamp_anomalies: Option<Vec<TimestampAnomaly>>,
+    pub authorship: Option<AuthorshipReport>,
+    pub activity: Option<Activity>,
 
     pub total_commits: Option<i32>,
     pub commits_task1: Option<Vec<String>>,
     pub commits_task2: Option<Vec<String>>,
+    pub commit_quality_task1: Option<CommitQuality>,
+    pub commit_quality_task2: Option<CommitQuality>,
     pub has_task1: Option<String>,
     pub has_task2: Option<String>,
     pub task1_main: Option<String>,
@@ -23,6 +39,45 @@ pub struct StudentProjectSubmission {
     pub final_commit_compile_task2: Option<bool>,
     pub successful_compiles_task1: Option<i32>,
     pub successful_compiles_task2: Option<i32>,
+    pub standards_task1: Option<StandardReport>,
+    pub standards_task2: Option<StandardReport>,
+    pub sanitizer_findings_task1: Option<Vec<SanitizerFinding>>,
+    pub sanitizer_findings_task2: Option<Vec<SanitizerFinding>>,
+    pub valgrind_task1: Option<ValgrindReport>,
+    pub valgrind_task2: Option<ValgrindReport>,
+    pub rule_violations_task1: Option<Vec<RuleViolation>>,
+    pub rule_violations_task2: Option<Vec<RuleViolation>>,
+    pub unit_tests_task1: Option<Vec<UnitTestResult>>,
+    pub unit_tests_task2: Option<Vec<UnitTestResult>>,
+    pub test_results_task1: Option<Vec<TestResult>>,
+    pub test_results_task2: Option<Vec<TestResult>>,
+    pub differential_task1: Option<DifferentialResult>,
+    pub differential_task2: Option<DifferentialResult>,
+    pub test_history_task1: Option<TestHistory>,
+    pub test_history_task2: Option<TestHistory>,
+    pub benchmark_task1: Option<BenchmarkResult>,
+    pub benchmark_task2: Option<BenchmarkResult>,
+    // why a benchmark run of the final commit crashed
+    pub benchmark_failure_task1: Option<String>,
+    pub benchmark_failure_task2: Option<String>,
+    pub coverage_task1: Option<CoverageReport>,
+    pub coverage_task2: Option<CoverageReport>,
+    pub metrics_task1: Option<CodeMetrics>,
+    pub metrics_task2: Option<CodeMetrics>,
+    // oldest commit first
+    pub metrics_history_task1: Option<Vec<CommitMetrics>>,
+    pub metrics_history_task2: Option<Vec<CommitMetrics>>,
+    pub style_task1: Option<StyleReport>,
+    pub style_task2: Option<StyleReport>,
+    pub deadline_task1: Option<DeadlineReport>,
+    pub deadline_task2: Option<DeadlineReport>,
+    // commit the repo is judged at with judge_at_deadline, and the HEAD it was cloned at
+    pub snapshot_commit: Option<String>,
+    pub head_commit: Option<String>,
+    // judge_at_deadline found nothing committed in time, so no task is judged
+    pub no_commit_before_deadline: bool,
+    pub head_task1: Option<HeadResult>,
+    pub head_task2: Option<HeadResult>,
 }
 
 impl StudentProjectSubmission {
@@ -30,13 +85,24 @@ impl StudentProjectSubmission {
         Self { 
             student_folder: name, 
             git_repo: None, 
+            same_repo_as: vec![],
             cloned: false, 
             jordan: jordan,
-            gcc_standard: None,
+            timeouts: vec![],
+            branch_report: None,
+            shared_history: None,
+            hygiene: None,
             last_commit_date: None,
+            cloned_at: None,
+            commit_infos: vec![],
+            timestamp_anomalies: None,
+            authorship: None,
+            activity: None,
             total_commits: None,
             commits_task1: None, 
             commits_task2: None, 
+            commit_quality_task1: None,
+            commit_quality_task2: None,
             has_task1: None, 
             has_task2: None, 
             task1_main: None,
@@ -47,6 +113,41 @@ impl StudentProjectSubmission {
             final_commit_compile_task2: None,
             successful_compiles_task1: None,
             successful_compiles_task2: None,
+            standards_task1: None,
+            standards_task2: None,
+            sanitizer_findings_task1: None,
+            sanitizer_findings_task2: None,
+            valgrind_task1: None,
+            valgrind_task2: None,
+            rule_violations_task1: None,
+            rule_violations_task2: None,
+            unit_tests_task1: None,
+            unit_tests_task2: None,
+            test_results_task1: None,
+            test_results_task2: None,
+            differential_task1: None,
+            differential_task2: None,
+            test_history_task1: None,
+            test_history_task2: None,
+            benchmark_task1: None,
+            benchmark_task2: None,
+            benchmark_failure_task1: None,
+            benchmark_failure_task2: None,
+            coverage_task1: None,
+            coverage_task2: None,
+            metrics_task1: None,
+            metrics_task2: None,
+            metrics_history_task1: None,
+            metrics_history_task2: None,
+            style_task1: None,
+            style_task2: None,
+            deadline_task1: None,
+            deadline_task2: None,
+            snapshot_commit: None,
+            head_commit: None,
+            no_commit_before_deadline: false,
+            head_task1: None,
+            head_task2: None,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,15 @@
+#[derive(Debug, Clone)]
+pub struct StyleIssue {
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StyleReport {
+    // lines with at least one issue, over all sources of the task
+    pub deviating_lines: usize,
+    pub issues: Vec<StyleIssue>,
+    // unified diff to the suggested formatting
+    pub diff: String,
+}
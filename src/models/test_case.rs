@@ -0,0 +1,78 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompareMode {
+    Exact,
+    // any run of whitespace equals any other
+    Whitespace,
+    CaseInsensitive,
+    // expected output is a regex the whole output must match
+    Regex,
+    // numbers may differ by the tolerance, other tokens must be equal
+    Float(f64),
+    UnorderedLines,
+}
+
+// a tolerance alone compares as float, neither set compares exactly
+pub fn resolve_compare(compare: Option<&CompareMode>, tolerance: Option<f64>) -> CompareMode {
+    match (compare, tolerance) {
+        (Some(CompareMode::Float(_)) | None, Some(tolerance)) => CompareMode::Float(tolerance),
+        (Some(compare), _) => compare.clone(),
+        (None, None) => CompareMode::Exact,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TestCase {
+    pub name: String,
+    pub args: Vec<String>,
+    // paths relative to where the judge runs
+    pub stdin: Option<String>,
+    // copied into the working directory of the run
+    pub files: Vec<String>,
+    pub exit_code: Option<i32>,
+    pub stdout: Option<String>,
+    pub stderr: Option<String>,
+    // unset on both falls back to the task
+    pub compare: Option<CompareMode>,
+    pub tolerance: Option<f64>,
+    // special judge that replaces the stdout comparison, C/C++ source or executable
+    pub checker: Option<String>,
+    pub weight: f64,
+    // hidden cases are not shown in per-student feedback
+    pub hidden: bool,
+}
+
+impl TestCase {
+    pub fn new(name: String) -> Self {
+        Self { 
+            name,
+            args: vec![],
+            stdin: None,
+            files: vec![],
+            exit_code: None,
+            stdout: None,
+            stderr: None,
+            compare: None,
+            tolerance: None,
+            checker: None,
+            weight: 1.0,
+            hidden: false,
+        }
+    }
+
+    pub fn compare_mode(&self) -> CompareMode {
+        resolve_compare(self.compare.as_ref(), self.tolerance)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TestResult {
+    pub name: String,
+    pub passed: bool,
+    // weight of the case if passed, a share of it for partial checker points
+    pub score: f64,
+    pub weight: f64,
+    pub hidden: bool,
+    pub timed_out: bool,
+    // reason of the failure or the message of the checker
+    pub message: Option<String>,
+}
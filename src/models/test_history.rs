@@ -0,0 +1,26 @@
+#[derive(Debug, Clone)]
+pub struct CommitTestRun {
+    pub commit: String,
+    // false if the commit does not build, every case fails then
+    pub built: bool,
+    // names of the passing test cases
+    pub passed: Vec<String>,
+}
+
+// a test case that passed in one commit and failed in a later one
+#[derive(Debug, Clone)]
+pub struct TestRegression {
+    pub test: String,
+    pub passed_in: String,
+    pub failed_in: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct TestHistory {
+    // number of test cases of the task
+    pub total: usize,
+    // oldest commit first
+    pub runs: Vec<CommitTestRun>,
+    pub first_all_passing: Option<String>,
+    pub regressions: Vec<TestRegression>,
+}
@@ -0,0 +1,7 @@
+#[derive(Debug, Clone)]
+pub struct TimestampAnomaly {
+    pub commit: String,
+    // parent_order, author_gap, identical, future or timezone_jump
+    pub kind: String,
+    pub message: String,
+}
@@ -0,0 +1,7 @@
+#[derive(Debug, Clone)]
+pub struct UnitTestResult {
+    pub name: String,
+    pub passed: bool,
+    // TAP diagnostics or the text after the name of a FAIL line
+    pub message: Option<String>,
+}
@@ -0,0 +1,9 @@
+#[derive(Debug, Clone, Default)]
+pub struct ValgrindReport {
+    // bytes, as reported by the leak check
+    pub definitely_lost: u64,
+    pub indirectly_lost: u64,
+    pub possibly_lost: u64,
+    pub invalid_reads: u32,
+    pub invalid_writes: u32,
+}